@@ -0,0 +1,36 @@
+-- This file should undo anything in `up.sql`
+
+DROP VIEW IF EXISTS poll_option_results;
+DROP VIEW IF EXISTS poll_results;
+
+ALTER TABLE votes
+    ADD COLUMN answer BOOLEAN NULL;
+
+UPDATE votes v
+SET answer = CASE WHEN o.preset_answer = 'ACCEPT' THEN TRUE WHEN o.preset_answer = 'DECLINE' THEN FALSE END
+FROM poll_options o
+WHERE o.id = v.poll_option_fk;
+
+ALTER TABLE votes
+    DROP COLUMN poll_option_fk;
+ALTER TABLE polls
+    DROP COLUMN poll_type;
+DROP TABLE IF EXISTS poll_options;
+
+CREATE VIEW poll_results AS
+(
+SELECT p.id,
+       p.sequenz_number,
+       p.voting_fk,
+       p.name,
+       p.description,
+       COUNT(CASE WHEN v.answer THEN 1 END)         AS votes_accept,
+       COUNT(CASE WHEN v.answer = FALSE THEN 1 END) AS votes_decline,
+       COUNT(v.id) - COUNT(CASE WHEN v.answer = TRUE THEN 1 END) - COUNT(CASE WHEN v.answer = FALSE THEN 1 END)
+                                                    AS votes_abstain,
+       COUNT(v.id)                                  AS votes_total
+FROM polls p
+         LEFT JOIN votes v ON p.id = v.poll_fk
+GROUP BY p.id, p.name, p.description, p.sequenz_number, p.voting_fk
+ORDER BY p.sequenz_number
+    );
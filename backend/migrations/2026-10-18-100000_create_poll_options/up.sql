@@ -0,0 +1,78 @@
+-- Your SQL goes here
+
+CREATE TABLE poll_options
+(
+    id             VARCHAR(36) PRIMARY KEY DEFAULT uuid_generate_v4(),
+    sequenz_number INT         NOT NULL,
+    poll_fk        VARCHAR(36) NOT NULL
+        CONSTRAINT poll_options_polls_id_fk
+            REFERENCES polls (id)
+            ON DELETE CASCADE,
+    name           VARCHAR(64) NOT NULL,
+    preset_answer  VARCHAR(16) NULL
+);
+
+ALTER TABLE polls
+    ADD COLUMN poll_type VARCHAR(32) NOT NULL DEFAULT 'YES_NO';
+
+INSERT INTO poll_options (sequenz_number, poll_fk, name, preset_answer)
+SELECT preset.sequenz_number, p.id, preset.name, preset.preset_answer
+FROM polls p
+         CROSS JOIN (VALUES (0, 'Accept', 'ACCEPT'),
+                            (10, 'Decline', 'DECLINE'),
+                            (20, 'Abstain', 'ABSTAIN')) AS preset (sequenz_number, name, preset_answer);
+
+ALTER TABLE votes
+    ADD COLUMN poll_option_fk VARCHAR(36) NULL
+        CONSTRAINT votes_poll_options_id_fk
+            REFERENCES poll_options (id)
+            ON DELETE CASCADE;
+
+UPDATE votes v
+SET poll_option_fk = o.id
+FROM poll_options o
+WHERE o.poll_fk = v.poll_fk
+  AND o.preset_answer = CASE WHEN v.answer THEN 'ACCEPT' WHEN v.answer = FALSE THEN 'DECLINE' ELSE 'ABSTAIN' END;
+
+ALTER TABLE votes
+    ALTER COLUMN poll_option_fk SET NOT NULL;
+
+DROP VIEW poll_results;
+
+ALTER TABLE votes
+    DROP COLUMN answer;
+
+CREATE VIEW poll_results AS
+(
+SELECT p.id,
+       p.sequenz_number,
+       p.voting_fk,
+       p.name,
+       p.description,
+       p.poll_type,
+       COUNT(CASE WHEN o.preset_answer = 'ACCEPT' THEN 1 END)  AS votes_accept,
+       COUNT(CASE WHEN o.preset_answer = 'DECLINE' THEN 1 END) AS votes_decline,
+       COUNT(CASE WHEN o.preset_answer = 'ABSTAIN' THEN 1 END) AS votes_abstain,
+       COUNT(v.id)                                             AS votes_total
+FROM polls p
+         LEFT JOIN votes v ON p.id = v.poll_fk
+         LEFT JOIN poll_options o ON o.id = v.poll_option_fk
+GROUP BY p.id, p.name, p.description, p.sequenz_number, p.voting_fk, p.poll_type
+ORDER BY p.sequenz_number
+    );
+
+CREATE VIEW poll_option_results AS
+(
+SELECT o.id,
+       o.sequenz_number,
+       o.poll_fk,
+       p.voting_fk,
+       o.name,
+       o.preset_answer,
+       COUNT(v.id) AS votes
+FROM poll_options o
+         INNER JOIN polls p ON p.id = o.poll_fk
+         LEFT JOIN votes v ON o.id = v.poll_option_fk
+GROUP BY o.id, o.sequenz_number, o.poll_fk, p.voting_fk, o.name, o.preset_answer
+ORDER BY o.sequenz_number
+    );
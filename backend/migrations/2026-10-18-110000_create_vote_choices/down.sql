@@ -0,0 +1,60 @@
+-- This file should undo anything in `up.sql`
+
+DROP VIEW IF EXISTS poll_option_results;
+DROP VIEW IF EXISTS poll_results;
+
+ALTER TABLE votes
+    ADD COLUMN poll_option_fk VARCHAR(36) NULL
+        CONSTRAINT votes_poll_options_id_fk
+            REFERENCES poll_options (id)
+            ON DELETE CASCADE;
+
+UPDATE votes v
+SET poll_option_fk = c.poll_option_fk
+FROM vote_choices c
+WHERE c.vote_fk = v.id
+  AND c.rank = 0;
+
+DELETE
+FROM votes
+WHERE poll_option_fk IS NULL;
+
+ALTER TABLE votes
+    ALTER COLUMN poll_option_fk SET NOT NULL;
+
+DROP TABLE IF EXISTS vote_choices;
+
+CREATE VIEW poll_results AS
+(
+SELECT p.id,
+       p.sequenz_number,
+       p.voting_fk,
+       p.name,
+       p.description,
+       p.poll_type,
+       COUNT(CASE WHEN o.preset_answer = 'ACCEPT' THEN 1 END)  AS votes_accept,
+       COUNT(CASE WHEN o.preset_answer = 'DECLINE' THEN 1 END) AS votes_decline,
+       COUNT(CASE WHEN o.preset_answer = 'ABSTAIN' THEN 1 END) AS votes_abstain,
+       COUNT(v.id)                                             AS votes_total
+FROM polls p
+         LEFT JOIN votes v ON p.id = v.poll_fk
+         LEFT JOIN poll_options o ON o.id = v.poll_option_fk
+GROUP BY p.id, p.name, p.description, p.sequenz_number, p.voting_fk, p.poll_type
+ORDER BY p.sequenz_number
+    );
+
+CREATE VIEW poll_option_results AS
+(
+SELECT o.id,
+       o.sequenz_number,
+       o.poll_fk,
+       p.voting_fk,
+       o.name,
+       o.preset_answer,
+       COUNT(v.id) AS votes
+FROM poll_options o
+         INNER JOIN polls p ON p.id = o.poll_fk
+         LEFT JOIN votes v ON o.id = v.poll_option_fk
+GROUP BY o.id, o.sequenz_number, o.poll_fk, p.voting_fk, o.name, o.preset_answer
+ORDER BY o.sequenz_number
+    );
@@ -0,0 +1,63 @@
+-- Your SQL goes here
+
+CREATE TABLE vote_choices
+(
+    id             VARCHAR(36) PRIMARY KEY DEFAULT uuid_generate_v4(),
+    vote_fk        VARCHAR(36) NOT NULL
+        CONSTRAINT vote_choices_votes_id_fk
+            REFERENCES votes (id)
+            ON DELETE CASCADE,
+    poll_option_fk VARCHAR(36) NOT NULL
+        CONSTRAINT vote_choices_poll_options_id_fk
+            REFERENCES poll_options (id)
+            ON DELETE CASCADE,
+    rank           INT         NOT NULL,
+    CONSTRAINT vote_choices_vote_option_uindex UNIQUE (vote_fk, poll_option_fk)
+);
+
+INSERT INTO vote_choices (vote_fk, poll_option_fk, rank)
+SELECT v.id, v.poll_option_fk, 0
+FROM votes v;
+
+DROP VIEW poll_option_results;
+DROP VIEW poll_results;
+
+ALTER TABLE votes
+    DROP COLUMN poll_option_fk;
+
+CREATE VIEW poll_results AS
+(
+SELECT p.id,
+       p.sequenz_number,
+       p.voting_fk,
+       p.name,
+       p.description,
+       p.poll_type,
+       COUNT(CASE WHEN o.preset_answer = 'ACCEPT' THEN 1 END)  AS votes_accept,
+       COUNT(CASE WHEN o.preset_answer = 'DECLINE' THEN 1 END) AS votes_decline,
+       COUNT(CASE WHEN o.preset_answer = 'ABSTAIN' THEN 1 END) AS votes_abstain,
+       COUNT(DISTINCT v.id)                                    AS votes_total
+FROM polls p
+         LEFT JOIN votes v ON p.id = v.poll_fk
+         LEFT JOIN vote_choices c ON v.id = c.vote_fk AND c.rank = 0
+         LEFT JOIN poll_options o ON o.id = c.poll_option_fk
+GROUP BY p.id, p.name, p.description, p.sequenz_number, p.voting_fk, p.poll_type
+ORDER BY p.sequenz_number
+    );
+
+-- For ranked polls the votes of an option are its first preferences.
+CREATE VIEW poll_option_results AS
+(
+SELECT o.id,
+       o.sequenz_number,
+       o.poll_fk,
+       p.voting_fk,
+       o.name,
+       o.preset_answer,
+       COUNT(c.id) AS votes
+FROM poll_options o
+         INNER JOIN polls p ON p.id = o.poll_fk
+         LEFT JOIN vote_choices c ON o.id = c.poll_option_fk AND c.rank = 0
+GROUP BY o.id, o.sequenz_number, o.poll_fk, p.voting_fk, o.name, o.preset_answer
+ORDER BY o.sequenz_number
+    );
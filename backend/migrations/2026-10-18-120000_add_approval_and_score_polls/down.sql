@@ -0,0 +1,27 @@
+-- This file should undo anything in `up.sql`
+
+DROP VIEW IF EXISTS poll_option_results;
+
+ALTER TABLE vote_choices
+    DROP COLUMN score;
+
+ALTER TABLE polls
+    DROP COLUMN min_approvals,
+    DROP COLUMN max_approvals,
+    DROP COLUMN max_score;
+
+CREATE VIEW poll_option_results AS
+(
+SELECT o.id,
+       o.sequenz_number,
+       o.poll_fk,
+       p.voting_fk,
+       o.name,
+       o.preset_answer,
+       COUNT(c.id) AS votes
+FROM poll_options o
+         INNER JOIN polls p ON p.id = o.poll_fk
+         LEFT JOIN vote_choices c ON o.id = c.poll_option_fk AND c.rank = 0
+GROUP BY o.id, o.sequenz_number, o.poll_fk, p.voting_fk, o.name, o.preset_answer
+ORDER BY o.sequenz_number
+    );
@@ -0,0 +1,29 @@
+-- Your SQL goes here
+
+ALTER TABLE polls
+    ADD COLUMN min_approvals INT NULL,
+    ADD COLUMN max_approvals INT NULL,
+    ADD COLUMN max_score     INT NULL;
+
+-- Approval and score ballots are not ordered, all of their choices have rank 0.
+ALTER TABLE vote_choices
+    ADD COLUMN score INT NULL;
+
+DROP VIEW poll_option_results;
+
+CREATE VIEW poll_option_results AS
+(
+SELECT o.id,
+       o.sequenz_number,
+       o.poll_fk,
+       p.voting_fk,
+       o.name,
+       o.preset_answer,
+       COUNT(c.id)                AS votes,
+       COALESCE(SUM(c.score), 0) AS score_total
+FROM poll_options o
+         INNER JOIN polls p ON p.id = o.poll_fk
+         LEFT JOIN vote_choices c ON o.id = c.poll_option_fk AND c.rank = 0
+GROUP BY o.id, o.sequenz_number, o.poll_fk, p.voting_fk, o.name, o.preset_answer
+ORDER BY o.sequenz_number
+    );
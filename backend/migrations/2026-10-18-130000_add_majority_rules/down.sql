@@ -0,0 +1,27 @@
+-- This file should undo anything in `up.sql`
+
+DROP VIEW IF EXISTS poll_results;
+
+ALTER TABLE polls
+    DROP COLUMN majority_rule,
+    DROP COLUMN quorum_percent;
+
+CREATE VIEW poll_results AS
+(
+SELECT p.id,
+       p.sequenz_number,
+       p.voting_fk,
+       p.name,
+       p.description,
+       p.poll_type,
+       COUNT(CASE WHEN o.preset_answer = 'ACCEPT' THEN 1 END)  AS votes_accept,
+       COUNT(CASE WHEN o.preset_answer = 'DECLINE' THEN 1 END) AS votes_decline,
+       COUNT(CASE WHEN o.preset_answer = 'ABSTAIN' THEN 1 END) AS votes_abstain,
+       COUNT(DISTINCT v.id)                                    AS votes_total
+FROM polls p
+         LEFT JOIN votes v ON p.id = v.poll_fk
+         LEFT JOIN vote_choices c ON v.id = c.vote_fk AND c.rank = 0
+         LEFT JOIN poll_options o ON o.id = c.poll_option_fk
+GROUP BY p.id, p.name, p.description, p.sequenz_number, p.voting_fk, p.poll_type
+ORDER BY p.sequenz_number
+    );
@@ -0,0 +1,54 @@
+-- This file should undo anything in `up.sql`
+
+DROP VIEW IF EXISTS poll_results;
+DROP VIEW IF EXISTS poll_option_results;
+
+ALTER TABLE polls
+    DROP COLUMN vote_counting;
+
+ALTER TABLE votes
+    DROP COLUMN weight;
+
+ALTER TABLE voters
+    DROP COLUMN weight;
+
+CREATE VIEW poll_results AS
+(
+SELECT p.id,
+       p.sequenz_number,
+       p.voting_fk,
+       p.name,
+       p.description,
+       p.poll_type,
+       p.majority_rule,
+       p.quorum_percent,
+       p.chair_decision_option_fk,
+       COUNT(CASE WHEN o.preset_answer = 'ACCEPT' THEN 1 END)  AS votes_accept,
+       COUNT(CASE WHEN o.preset_answer = 'DECLINE' THEN 1 END) AS votes_decline,
+       COUNT(CASE WHEN o.preset_answer = 'ABSTAIN' THEN 1 END) AS votes_abstain,
+       COUNT(DISTINCT v.id)                                    AS votes_total
+FROM polls p
+         LEFT JOIN votes v ON p.id = v.poll_fk
+         LEFT JOIN vote_choices c ON v.id = c.vote_fk AND c.rank = 0
+         LEFT JOIN poll_options o ON o.id = c.poll_option_fk
+GROUP BY p.id, p.name, p.description, p.sequenz_number, p.voting_fk, p.poll_type, p.majority_rule, p.quorum_percent,
+         p.chair_decision_option_fk
+ORDER BY p.sequenz_number
+    );
+
+CREATE VIEW poll_option_results AS
+(
+SELECT o.id,
+       o.sequenz_number,
+       o.poll_fk,
+       p.voting_fk,
+       o.name,
+       o.preset_answer,
+       COUNT(c.id)                AS votes,
+       COALESCE(SUM(c.score), 0) AS score_total
+FROM poll_options o
+         INNER JOIN polls p ON p.id = o.poll_fk
+         LEFT JOIN vote_choices c ON o.id = c.poll_option_fk AND c.rank = 0
+GROUP BY o.id, o.sequenz_number, o.poll_fk, p.voting_fk, o.name, o.preset_answer
+ORDER BY o.sequenz_number
+    );
@@ -0,0 +1,38 @@
+-- This file should undo anything in `up.sql`
+
+DROP VIEW IF EXISTS poll_results;
+
+DROP TABLE delegations;
+
+ALTER TABLE polls
+    DROP COLUMN topic;
+
+CREATE VIEW poll_results AS
+(
+SELECT p.id,
+       p.sequenz_number,
+       p.voting_fk,
+       p.name,
+       p.description,
+       p.poll_type,
+       p.majority_rule,
+       p.quorum_percent,
+       p.chair_decision_option_fk,
+       p.vote_counting,
+       COUNT(CASE WHEN o.preset_answer = 'ACCEPT' THEN 1 END)                       AS votes_accept,
+       COUNT(CASE WHEN o.preset_answer = 'DECLINE' THEN 1 END)                      AS votes_decline,
+       COUNT(CASE WHEN o.preset_answer = 'ABSTAIN' THEN 1 END)                      AS votes_abstain,
+       COUNT(DISTINCT v.id)                                                         AS votes_total,
+       COALESCE(SUM(CASE WHEN o.preset_answer = 'ACCEPT' THEN v.weight END), 0)     AS weighted_votes_accept,
+       COALESCE(SUM(CASE WHEN o.preset_answer = 'DECLINE' THEN v.weight END), 0)    AS weighted_votes_decline,
+       COALESCE(SUM(CASE WHEN o.preset_answer = 'ABSTAIN' THEN v.weight END), 0)    AS weighted_votes_abstain,
+       (SELECT COALESCE(SUM(w.weight), 0) FROM votes w WHERE w.poll_fk = p.id)      AS weighted_votes_total,
+       COUNT(DISTINCT CASE WHEN v.proxy_voter_fk IS NOT NULL THEN v.id END)         AS proxy_votes
+FROM polls p
+         LEFT JOIN votes v ON p.id = v.poll_fk
+         LEFT JOIN vote_choices c ON v.id = c.vote_fk AND c.rank = 0
+         LEFT JOIN poll_options o ON o.id = c.poll_option_fk
+GROUP BY p.id, p.name, p.description, p.sequenz_number, p.voting_fk, p.poll_type, p.majority_rule, p.quorum_percent,
+         p.chair_decision_option_fk, p.vote_counting
+ORDER BY p.sequenz_number
+    );
@@ -0,0 +1,57 @@
+-- This file should undo anything in `up.sql`
+
+DROP VIEW IF EXISTS poll_results;
+
+-- Secret ballots can not be assigned to their voters anymore.
+DELETE
+FROM votes
+WHERE voter_fk IS NULL;
+
+ALTER TABLE votes
+    ALTER COLUMN voter_fk SET NOT NULL,
+    ADD COLUMN proxy_voter_fk VARCHAR(36) NULL
+        CONSTRAINT votes_proxy_voters_id_fk
+            REFERENCES voters (id)
+            ON DELETE SET NULL;
+
+UPDATE votes v
+SET proxy_voter_fk = pa.proxy_voter_fk
+FROM participations pa
+WHERE pa.poll_fk = v.poll_fk
+  AND pa.voter_fk = v.voter_fk;
+
+DROP TABLE participations;
+
+ALTER TABLE votings
+    DROP COLUMN secret;
+
+CREATE VIEW poll_results AS
+(
+SELECT p.id,
+       p.sequenz_number,
+       p.voting_fk,
+       p.name,
+       p.description,
+       p.poll_type,
+       p.majority_rule,
+       p.quorum_percent,
+       p.chair_decision_option_fk,
+       p.vote_counting,
+       p.topic,
+       COUNT(CASE WHEN o.preset_answer = 'ACCEPT' THEN 1 END)                       AS votes_accept,
+       COUNT(CASE WHEN o.preset_answer = 'DECLINE' THEN 1 END)                      AS votes_decline,
+       COUNT(CASE WHEN o.preset_answer = 'ABSTAIN' THEN 1 END)                      AS votes_abstain,
+       COUNT(DISTINCT v.id)                                                         AS votes_total,
+       COALESCE(SUM(CASE WHEN o.preset_answer = 'ACCEPT' THEN v.weight END), 0)     AS weighted_votes_accept,
+       COALESCE(SUM(CASE WHEN o.preset_answer = 'DECLINE' THEN v.weight END), 0)    AS weighted_votes_decline,
+       COALESCE(SUM(CASE WHEN o.preset_answer = 'ABSTAIN' THEN v.weight END), 0)    AS weighted_votes_abstain,
+       (SELECT COALESCE(SUM(w.weight), 0) FROM votes w WHERE w.poll_fk = p.id)      AS weighted_votes_total,
+       COUNT(DISTINCT CASE WHEN v.proxy_voter_fk IS NOT NULL THEN v.id END)         AS proxy_votes
+FROM polls p
+         LEFT JOIN votes v ON p.id = v.poll_fk
+         LEFT JOIN vote_choices c ON v.id = c.vote_fk AND c.rank = 0
+         LEFT JOIN poll_options o ON o.id = c.poll_option_fk
+GROUP BY p.id, p.name, p.description, p.sequenz_number, p.voting_fk, p.poll_type, p.majority_rule, p.quorum_percent,
+         p.chair_decision_option_fk, p.vote_counting, p.topic
+ORDER BY p.sequenz_number
+    );
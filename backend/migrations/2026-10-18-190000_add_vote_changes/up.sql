@@ -0,0 +1,72 @@
+-- Your SQL goes here
+
+ALTER TABLE votings
+    ADD COLUMN allow_vote_change BOOLEAN NOT NULL DEFAULT FALSE,
+    ADD COLUMN max_vote_changes  INT     NULL;
+
+-- Replaced ballots are kept, only the ballot without replaced_at counts.
+-- Secret ballots have neither timestamp.
+ALTER TABLE votes
+    ADD COLUMN voted_at    TIMESTAMP NULL,
+    ADD COLUMN replaced_at TIMESTAMP NULL;
+
+-- A voter has at most one current ballot per poll.
+CREATE UNIQUE INDEX votes_current_ballot_uindex
+    ON votes (poll_fk, voter_fk)
+    WHERE replaced_at IS NULL;
+
+DROP VIEW poll_results;
+DROP VIEW poll_option_results;
+
+CREATE VIEW poll_results AS
+(
+SELECT p.id,
+       p.sequenz_number,
+       p.voting_fk,
+       p.name,
+       p.description,
+       p.poll_type,
+       p.majority_rule,
+       p.quorum_percent,
+       p.chair_decision_option_fk,
+       p.vote_counting,
+       p.topic,
+       COUNT(CASE WHEN o.preset_answer = 'ACCEPT' THEN 1 END)                       AS votes_accept,
+       COUNT(CASE WHEN o.preset_answer = 'DECLINE' THEN 1 END)                      AS votes_decline,
+       COUNT(CASE WHEN o.preset_answer = 'ABSTAIN' THEN 1 END)                      AS votes_abstain,
+       COUNT(DISTINCT v.id)                                                         AS votes_total,
+       COALESCE(SUM(CASE WHEN o.preset_answer = 'ACCEPT' THEN v.weight END), 0)     AS weighted_votes_accept,
+       COALESCE(SUM(CASE WHEN o.preset_answer = 'DECLINE' THEN v.weight END), 0)    AS weighted_votes_decline,
+       COALESCE(SUM(CASE WHEN o.preset_answer = 'ABSTAIN' THEN v.weight END), 0)    AS weighted_votes_abstain,
+       (SELECT COALESCE(SUM(w.weight), 0) FROM votes w WHERE w.poll_fk = p.id AND w.replaced_at IS NULL)
+                                                                                    AS weighted_votes_total,
+       (SELECT COUNT(*) FROM participations pa WHERE pa.poll_fk = p.id AND pa.proxy_voter_fk IS NOT NULL)
+                                                                                    AS proxy_votes
+FROM polls p
+         LEFT JOIN votes v ON p.id = v.poll_fk AND v.replaced_at IS NULL
+         LEFT JOIN vote_choices c ON v.id = c.vote_fk AND c.rank = 0
+         LEFT JOIN poll_options o ON o.id = c.poll_option_fk
+GROUP BY p.id, p.name, p.description, p.sequenz_number, p.voting_fk, p.poll_type, p.majority_rule, p.quorum_percent,
+         p.chair_decision_option_fk, p.vote_counting, p.topic
+ORDER BY p.sequenz_number
+    );
+
+CREATE VIEW poll_option_results AS
+(
+SELECT o.id,
+       o.sequenz_number,
+       o.poll_fk,
+       p.voting_fk,
+       o.name,
+       o.preset_answer,
+       COUNT(c.id)                           AS votes,
+       COALESCE(SUM(c.score), 0)             AS score_total,
+       COALESCE(SUM(v.weight), 0)            AS weighted_votes,
+       COALESCE(SUM(c.score * v.weight), 0) AS weighted_score_total
+FROM poll_options o
+         INNER JOIN polls p ON p.id = o.poll_fk
+         LEFT JOIN (vote_choices c INNER JOIN votes v ON v.id = c.vote_fk AND v.replaced_at IS NULL)
+                   ON o.id = c.poll_option_fk AND c.rank = 0
+GROUP BY o.id, o.sequenz_number, o.poll_fk, p.voting_fk, o.name, o.preset_answer
+ORDER BY o.sequenz_number
+    );
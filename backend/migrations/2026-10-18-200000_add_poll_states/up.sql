@@ -0,0 +1,57 @@
+-- Your SQL goes here
+
+DROP VIEW poll_results;
+
+ALTER TABLE polls
+    ADD COLUMN state     VARCHAR(16) NOT NULL DEFAULT 'DRAFT',
+    ADD COLUMN opened_at TIMESTAMP   NULL,
+    ADD COLUMN closed_at TIMESTAMP   NULL;
+
+-- The active poll is open, every other poll that already has votes was
+-- closed at some unknown time before.
+UPDATE polls p
+SET state     = CASE WHEN ranked.poll_index = v.active_poll_index THEN 'OPEN' ELSE 'CLOSED' END,
+    opened_at = timezone('utc', now()),
+    closed_at = CASE WHEN ranked.poll_index = v.active_poll_index THEN NULL ELSE timezone('utc', now()) END
+FROM (SELECT id, ROW_NUMBER() OVER (PARTITION BY voting_fk ORDER BY sequenz_number) - 1 AS poll_index
+      FROM polls) ranked,
+     votings v
+WHERE ranked.id = p.id
+  AND v.id = p.voting_fk
+  AND (ranked.poll_index = v.active_poll_index OR EXISTS(SELECT 1 FROM votes WHERE votes.poll_fk = p.id));
+
+CREATE VIEW poll_results AS
+(
+SELECT p.id,
+       p.sequenz_number,
+       p.voting_fk,
+       p.name,
+       p.description,
+       p.poll_type,
+       p.majority_rule,
+       p.quorum_percent,
+       p.chair_decision_option_fk,
+       p.vote_counting,
+       p.topic,
+       p.state,
+       p.opened_at,
+       p.closed_at,
+       COUNT(CASE WHEN o.preset_answer = 'ACCEPT' THEN 1 END)                       AS votes_accept,
+       COUNT(CASE WHEN o.preset_answer = 'DECLINE' THEN 1 END)                      AS votes_decline,
+       COUNT(CASE WHEN o.preset_answer = 'ABSTAIN' THEN 1 END)                      AS votes_abstain,
+       COUNT(DISTINCT v.id)                                                         AS votes_total,
+       COALESCE(SUM(CASE WHEN o.preset_answer = 'ACCEPT' THEN v.weight END), 0)     AS weighted_votes_accept,
+       COALESCE(SUM(CASE WHEN o.preset_answer = 'DECLINE' THEN v.weight END), 0)    AS weighted_votes_decline,
+       COALESCE(SUM(CASE WHEN o.preset_answer = 'ABSTAIN' THEN v.weight END), 0)    AS weighted_votes_abstain,
+       (SELECT COALESCE(SUM(w.weight), 0) FROM votes w WHERE w.poll_fk = p.id AND w.replaced_at IS NULL)
+                                                                                    AS weighted_votes_total,
+       (SELECT COUNT(*) FROM participations pa WHERE pa.poll_fk = p.id AND pa.proxy_voter_fk IS NOT NULL)
+                                                                                    AS proxy_votes
+FROM polls p
+         LEFT JOIN votes v ON p.id = v.poll_fk AND v.replaced_at IS NULL
+         LEFT JOIN vote_choices c ON v.id = c.vote_fk AND c.rank = 0
+         LEFT JOIN poll_options o ON o.id = c.poll_option_fk
+GROUP BY p.id, p.name, p.description, p.sequenz_number, p.voting_fk, p.poll_type, p.majority_rule, p.quorum_percent,
+         p.chair_decision_option_fk, p.vote_counting, p.topic, p.state, p.opened_at, p.closed_at
+ORDER BY p.sequenz_number
+    );
@@ -0,0 +1,15 @@
+-- This file should undo anything in `up.sql`
+
+ALTER TABLE votings
+    ADD COLUMN active_poll_index INT NULL;
+
+UPDATE votings v
+SET active_poll_index = (SELECT ranked.poll_index
+                         FROM (SELECT id,
+                                      state,
+                                      ROW_NUMBER() OVER (ORDER BY sequenz_number) - 1 AS poll_index
+                               FROM polls
+                               WHERE voting_fk = v.id) ranked
+                         WHERE ranked.state = 'OPEN'
+                         ORDER BY ranked.poll_index
+                         LIMIT 1);
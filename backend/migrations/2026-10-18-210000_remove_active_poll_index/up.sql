@@ -0,0 +1,5 @@
+-- Your SQL goes here
+
+-- The polls in state OPEN replace the single active poll.
+ALTER TABLE votings
+    DROP COLUMN active_poll_index;
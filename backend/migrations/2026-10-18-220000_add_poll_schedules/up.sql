@@ -0,0 +1,55 @@
+-- Your SQL goes here
+
+DROP VIEW poll_results;
+
+-- opens_at and closes_at are applied by the scheduler, the final result is
+-- recorded whenever a poll is closed.
+ALTER TABLE polls
+    ADD COLUMN opens_at               TIMESTAMP   NULL,
+    ADD COLUMN closes_at              TIMESTAMP   NULL,
+    ADD COLUMN final_status           VARCHAR(32) NULL,
+    ADD COLUMN final_winner_option_fk VARCHAR(36) NULL
+        CONSTRAINT polls_final_winner_poll_options_id_fk
+            REFERENCES poll_options (id)
+            ON DELETE SET NULL;
+
+CREATE VIEW poll_results AS
+(
+SELECT p.id,
+       p.sequenz_number,
+       p.voting_fk,
+       p.name,
+       p.description,
+       p.poll_type,
+       p.majority_rule,
+       p.quorum_percent,
+       p.chair_decision_option_fk,
+       p.vote_counting,
+       p.topic,
+       p.state,
+       p.opened_at,
+       p.closed_at,
+       p.opens_at,
+       p.closes_at,
+       p.final_status,
+       p.final_winner_option_fk,
+       COUNT(CASE WHEN o.preset_answer = 'ACCEPT' THEN 1 END)                       AS votes_accept,
+       COUNT(CASE WHEN o.preset_answer = 'DECLINE' THEN 1 END)                      AS votes_decline,
+       COUNT(CASE WHEN o.preset_answer = 'ABSTAIN' THEN 1 END)                      AS votes_abstain,
+       COUNT(DISTINCT v.id)                                                         AS votes_total,
+       COALESCE(SUM(CASE WHEN o.preset_answer = 'ACCEPT' THEN v.weight END), 0)     AS weighted_votes_accept,
+       COALESCE(SUM(CASE WHEN o.preset_answer = 'DECLINE' THEN v.weight END), 0)    AS weighted_votes_decline,
+       COALESCE(SUM(CASE WHEN o.preset_answer = 'ABSTAIN' THEN v.weight END), 0)    AS weighted_votes_abstain,
+       (SELECT COALESCE(SUM(w.weight), 0) FROM votes w WHERE w.poll_fk = p.id AND w.replaced_at IS NULL)
+                                                                                    AS weighted_votes_total,
+       (SELECT COUNT(*) FROM participations pa WHERE pa.poll_fk = p.id AND pa.proxy_voter_fk IS NOT NULL)
+                                                                                    AS proxy_votes
+FROM polls p
+         LEFT JOIN votes v ON p.id = v.poll_fk AND v.replaced_at IS NULL
+         LEFT JOIN vote_choices c ON v.id = c.vote_fk AND c.rank = 0
+         LEFT JOIN poll_options o ON o.id = c.poll_option_fk
+GROUP BY p.id, p.name, p.description, p.sequenz_number, p.voting_fk, p.poll_type, p.majority_rule, p.quorum_percent,
+         p.chair_decision_option_fk, p.vote_counting, p.topic, p.state, p.opened_at, p.closed_at, p.opens_at,
+         p.closes_at, p.final_status, p.final_winner_option_fk
+ORDER BY p.sequenz_number
+    );
@@ -0,0 +1,5 @@
+-- Your SQL goes here
+
+-- Archived votings are read-only and purged after the retention period.
+ALTER TABLE votings
+    ADD COLUMN archived_at TIMESTAMP NULL;
@@ -0,0 +1,10 @@
+-- Your SQL goes here
+
+-- The definition is the JSON of a create voting request.
+CREATE TABLE voting_templates
+(
+    id             VARCHAR(36) PRIMARY KEY DEFAULT uuid_generate_v4(),
+    name           VARCHAR(60) NOT NULL UNIQUE,
+    admin_key_hash VARCHAR(64) NOT NULL,
+    definition     TEXT        NOT NULL
+);
@@ -0,0 +1,4 @@
+-- Your SQL goes here
+
+ALTER TABLE voters
+    ADD COLUMN email VARCHAR(254) NULL;
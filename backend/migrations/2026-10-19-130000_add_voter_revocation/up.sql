@@ -0,0 +1,4 @@
+-- Your SQL goes here
+
+ALTER TABLE voters
+    ADD COLUMN revoked_at TIMESTAMP NULL;
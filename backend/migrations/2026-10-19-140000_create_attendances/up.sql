@@ -0,0 +1,67 @@
+-- Your SQL goes here
+
+DROP VIEW poll_results;
+
+-- A voter is present from checked_in_at until checked_out_at.
+CREATE TABLE attendances
+(
+    id             VARCHAR(36) PRIMARY KEY DEFAULT uuid_generate_v4(),
+    voting_fk      VARCHAR(36) NOT NULL
+        CONSTRAINT attendances_votings_id_fk
+            REFERENCES votings (id)
+            ON DELETE CASCADE,
+    voter_fk       VARCHAR(36) NOT NULL
+        CONSTRAINT attendances_voters_id_fk
+            REFERENCES voters (id)
+            ON DELETE CASCADE,
+    checked_in_at  TIMESTAMP   NOT NULL,
+    checked_out_at TIMESTAMP   NULL
+);
+
+-- Quorum and majority of these polls only count the voters present when the
+-- poll opened.
+ALTER TABLE polls
+    ADD COLUMN present_voters_only BOOLEAN NOT NULL DEFAULT FALSE;
+
+CREATE VIEW poll_results AS
+(
+SELECT p.id,
+       p.sequenz_number,
+       p.voting_fk,
+       p.name,
+       p.description,
+       p.poll_type,
+       p.majority_rule,
+       p.quorum_percent,
+       p.chair_decision_option_fk,
+       p.vote_counting,
+       p.topic,
+       p.state,
+       p.opened_at,
+       p.closed_at,
+       p.opens_at,
+       p.closes_at,
+       p.final_status,
+       p.final_winner_option_fk,
+       p.time_limit_seconds,
+       p.present_voters_only,
+       COUNT(CASE WHEN o.preset_answer = 'ACCEPT' THEN 1 END)                       AS votes_accept,
+       COUNT(CASE WHEN o.preset_answer = 'DECLINE' THEN 1 END)                      AS votes_decline,
+       COUNT(CASE WHEN o.preset_answer = 'ABSTAIN' THEN 1 END)                      AS votes_abstain,
+       COUNT(DISTINCT v.id)                                                         AS votes_total,
+       COALESCE(SUM(CASE WHEN o.preset_answer = 'ACCEPT' THEN v.weight END), 0)     AS weighted_votes_accept,
+       COALESCE(SUM(CASE WHEN o.preset_answer = 'DECLINE' THEN v.weight END), 0)    AS weighted_votes_decline,
+       COALESCE(SUM(CASE WHEN o.preset_answer = 'ABSTAIN' THEN v.weight END), 0)    AS weighted_votes_abstain,
+       (SELECT COALESCE(SUM(w.weight), 0) FROM votes w WHERE w.poll_fk = p.id AND w.replaced_at IS NULL)
+                                                                                    AS weighted_votes_total,
+       (SELECT COUNT(*) FROM participations pa WHERE pa.poll_fk = p.id AND pa.proxy_voter_fk IS NOT NULL)
+                                                                                    AS proxy_votes
+FROM polls p
+         LEFT JOIN votes v ON p.id = v.poll_fk AND v.replaced_at IS NULL
+         LEFT JOIN vote_choices c ON v.id = c.vote_fk AND c.rank = 0
+         LEFT JOIN poll_options o ON o.id = c.poll_option_fk
+GROUP BY p.id, p.name, p.description, p.sequenz_number, p.voting_fk, p.poll_type, p.majority_rule, p.quorum_percent,
+         p.chair_decision_option_fk, p.vote_counting, p.topic, p.state, p.opened_at, p.closed_at, p.opens_at,
+         p.closes_at, p.final_status, p.final_winner_option_fk, p.time_limit_seconds, p.present_voters_only
+ORDER BY p.sequenz_number
+    );
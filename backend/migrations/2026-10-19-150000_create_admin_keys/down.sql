@@ -0,0 +1,18 @@
+-- This file should undo anything in `up.sql`
+
+ALTER TABLE votings
+    ADD COLUMN admin_key_hash VARCHAR(64) NULL;
+
+-- Only one key per voting survives, the oldest full admin key.
+UPDATE votings
+SET admin_key_hash = (SELECT k.key_hash
+                      FROM admin_keys k
+                      WHERE k.voting_fk = votings.id
+                        AND k.scope = 'FULL'
+                      ORDER BY k.created_at
+                      LIMIT 1);
+
+ALTER TABLE votings
+    ALTER COLUMN admin_key_hash SET NOT NULL;
+
+DROP TABLE admin_keys;
@@ -0,0 +1,21 @@
+-- Your SQL goes here
+
+CREATE TABLE admin_keys
+(
+    id         VARCHAR(36) PRIMARY KEY DEFAULT uuid_generate_v4(),
+    voting_fk  VARCHAR(36) NOT NULL
+        CONSTRAINT admin_keys_votings_id_fk
+            REFERENCES votings (id)
+            ON DELETE CASCADE,
+    key_hash   VARCHAR(64) NOT NULL UNIQUE,
+    label      VARCHAR(60) NOT NULL,
+    scope      VARCHAR(10) NOT NULL,
+    created_at TIMESTAMP   NOT NULL DEFAULT (now() AT TIME ZONE 'utc')
+);
+
+INSERT INTO admin_keys (voting_fk, key_hash, label, scope)
+SELECT id, admin_key_hash, 'Chair', 'FULL'
+FROM votings;
+
+ALTER TABLE votings
+    DROP COLUMN admin_key_hash;
@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+
+ALTER TABLE votings
+    DROP COLUMN recovery_secret_hash;
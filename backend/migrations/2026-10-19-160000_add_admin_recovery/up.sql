@@ -0,0 +1,6 @@
+-- Your SQL goes here
+
+-- The recovery secret issues a new FULL admin key when the admin keys are lost
+-- or leaked.
+ALTER TABLE votings
+    ADD COLUMN recovery_secret_hash VARCHAR(64) NULL;
@@ -0,0 +1,113 @@
+-- Your SQL goes here
+
+-- The voter, weight and timestamps of a ballot are only kept in votings that
+-- are not secret. A secret ballot has no row here and counts with weight 1.
+CREATE TABLE named_votes
+(
+    vote_fk     VARCHAR(36) PRIMARY KEY
+        CONSTRAINT named_votes_votes_id_fk
+            REFERENCES votes (id)
+            ON DELETE CASCADE,
+    poll_fk     VARCHAR(36) NOT NULL
+        CONSTRAINT named_votes_polls_id_fk
+            REFERENCES polls (id)
+            ON DELETE CASCADE,
+    voter_fk    VARCHAR(36) NOT NULL
+        CONSTRAINT named_votes_voters_id_fk
+            REFERENCES voters (id)
+            ON DELETE CASCADE,
+    weight      INT         NOT NULL,
+    voted_at    TIMESTAMP   NOT NULL,
+    replaced_at TIMESTAMP   NULL
+);
+
+-- A voter has at most one current ballot per poll.
+CREATE UNIQUE INDEX named_votes_current_ballot_uindex
+    ON named_votes (poll_fk, voter_fk)
+    WHERE replaced_at IS NULL;
+
+INSERT INTO named_votes (vote_fk, poll_fk, voter_fk, weight, voted_at, replaced_at)
+SELECT id, poll_fk, voter_fk, weight, COALESCE(voted_at, timezone('utc', now())), replaced_at
+FROM votes
+WHERE voter_fk IS NOT NULL;
+
+DROP VIEW poll_results;
+DROP VIEW poll_option_results;
+
+DROP INDEX votes_current_ballot_uindex;
+
+ALTER TABLE votes
+    DROP COLUMN voter_fk,
+    DROP COLUMN weight,
+    DROP COLUMN voted_at,
+    DROP COLUMN replaced_at;
+
+CREATE VIEW poll_results AS
+(
+SELECT p.id,
+       p.sequenz_number,
+       p.voting_fk,
+       p.name,
+       p.description,
+       p.poll_type,
+       p.majority_rule,
+       p.quorum_percent,
+       p.chair_decision_option_fk,
+       p.vote_counting,
+       p.topic,
+       p.state,
+       p.opened_at,
+       p.closed_at,
+       p.opens_at,
+       p.closes_at,
+       p.final_status,
+       p.final_winner_option_fk,
+       p.time_limit_seconds,
+       p.present_voters_only,
+       COUNT(CASE WHEN o.preset_answer = 'ACCEPT' THEN 1 END)                       AS votes_accept,
+       COUNT(CASE WHEN o.preset_answer = 'DECLINE' THEN 1 END)                      AS votes_decline,
+       COUNT(CASE WHEN o.preset_answer = 'ABSTAIN' THEN 1 END)                      AS votes_abstain,
+       COUNT(DISTINCT v.id)                                                         AS votes_total,
+       COALESCE(SUM(CASE WHEN o.preset_answer = 'ACCEPT' THEN COALESCE(n.weight, 1) END), 0)     AS weighted_votes_accept,
+       COALESCE(SUM(CASE WHEN o.preset_answer = 'DECLINE' THEN COALESCE(n.weight, 1) END), 0)    AS weighted_votes_decline,
+       COALESCE(SUM(CASE WHEN o.preset_answer = 'ABSTAIN' THEN COALESCE(n.weight, 1) END), 0)    AS weighted_votes_abstain,
+       (SELECT COALESCE(SUM(COALESCE(wn.weight, 1)), 0)
+        FROM votes w
+                 LEFT JOIN named_votes wn ON w.id = wn.vote_fk
+        WHERE w.poll_fk = p.id
+          AND wn.replaced_at IS NULL)                                               AS weighted_votes_total,
+       (SELECT COUNT(*) FROM participations pa WHERE pa.poll_fk = p.id AND pa.proxy_voter_fk IS NOT NULL)
+                                                                                    AS proxy_votes
+FROM polls p
+         LEFT JOIN (votes v LEFT JOIN named_votes n ON v.id = n.vote_fk)
+                   ON p.id = v.poll_fk AND n.replaced_at IS NULL
+         LEFT JOIN vote_choices c ON v.id = c.vote_fk AND c.rank = 0
+         LEFT JOIN poll_options o ON o.id = c.poll_option_fk
+GROUP BY p.id, p.name, p.description, p.sequenz_number, p.voting_fk, p.poll_type, p.majority_rule, p.quorum_percent,
+         p.chair_decision_option_fk, p.vote_counting, p.topic, p.state, p.opened_at, p.closed_at, p.opens_at,
+         p.closes_at, p.final_status, p.final_winner_option_fk, p.time_limit_seconds, p.present_voters_only
+ORDER BY p.sequenz_number
+    );
+
+CREATE VIEW poll_option_results AS
+(
+SELECT o.id,
+       o.sequenz_number,
+       o.poll_fk,
+       p.voting_fk,
+       o.name,
+       o.preset_answer,
+       COUNT(c.id)                                                           AS votes,
+       COALESCE(SUM(c.score), 0)                                             AS score_total,
+       -- Without a choice the LEFT JOIN gives one row of NULLs, which must
+       -- not count as a ballot of weight 1.
+       COALESCE(SUM(CASE WHEN c.id IS NOT NULL THEN COALESCE(n.weight, 1) END), 0) AS weighted_votes,
+       COALESCE(SUM(c.score * COALESCE(n.weight, 1)), 0)                    AS weighted_score_total
+FROM poll_options o
+         INNER JOIN polls p ON p.id = o.poll_fk
+         LEFT JOIN (vote_choices c INNER JOIN votes v ON v.id = c.vote_fk
+             LEFT JOIN named_votes n ON v.id = n.vote_fk)
+                   ON o.id = c.poll_option_fk AND c.rank = 0 AND n.replaced_at IS NULL
+GROUP BY o.id, o.sequenz_number, o.poll_fk, p.voting_fk, o.name, o.preset_answer
+ORDER BY o.sequenz_number
+    );
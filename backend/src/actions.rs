@@ -1,4 +1,5 @@
 pub mod check;
+pub mod delete;
 pub mod find;
 pub mod insert;
 pub mod update;
@@ -1,5 +1,6 @@
 use crate::models::*;
 use crate::pool::DbConn;
+use crate::types::AdminScope;
 use crate::utils::{AuthenticatedUser, ErrorResponse};
 
 use crate::actions::find::*;
@@ -28,13 +29,96 @@ pub fn check_if_voter(
 
 #[inline(always)]
 pub fn check_if_voting_admin(
+    conn: &DbConn,
     voting: Voting,
     user: &AuthenticatedUser,
+    scope: AdminScope,
 ) -> Result<Voting, ErrorResponse> {
-    match user.key_hash.to_string().eq(&voting.admin_key_hash) {
+    match find_admin_key(conn, &voting.id, user)? {
+        Some(admin_key) if admin_key.scope.allows(scope) => Ok(voting),
+        Some(admin_key) => Err(ErrorResponse {
+            reason: format!(
+                "Admin key with label: {} does not have the scope {} for voting with id: {}",
+                admin_key.label,
+                scope.as_str(),
+                voting.id
+            ),
+            status: Status::Forbidden,
+        }),
+        None => Err(ErrorResponse {
+            reason: format!("Admin key is not correct for voting with id: {}", voting.id),
+            status: Status::Unauthorized,
+        }),
+    }
+}
+
+#[inline(always)]
+pub fn check_if_not_archived(voting: Voting) -> Result<Voting, ErrorResponse> {
+    match voting.archived_at {
+        None => Ok(voting),
+        Some(_) => Err(ErrorResponse {
+            reason: format!("Voting with id: {} is archived and read-only", voting.id),
+            status: Status::BadRequest,
+        }),
+    }
+}
+
+#[inline(always)]
+pub fn check_if_not_revoked(voter: Voter) -> Result<Voter, ErrorResponse> {
+    match voter.revoked_at {
+        None => Ok(voter),
+        Some(_) => Err(ErrorResponse {
+            reason: format!("Voter with id: {} is revoked", voter.id),
+            status: Status::Forbidden,
+        }),
+    }
+}
+
+/// The key of a voter authenticates them for every voting, so routes acting on
+/// the authenticated voter must check that they belong to the voting.
+#[inline(always)]
+pub fn check_if_voting_voter(voting: &Voting, voter: Voter) -> Result<Voter, ErrorResponse> {
+    match voter.voting_fk == voting.id {
+        true => Ok(voter),
+        false => Err(ErrorResponse {
+            reason: format!(
+                "Voter with id: {} is not in voting with id: {}",
+                voter.id, voting.id
+            ),
+            status: Status::Forbidden,
+        }),
+    }
+}
+
+#[inline(always)]
+pub fn check_if_template_admin(
+    template: VotingTemplate,
+    user: &AuthenticatedUser,
+) -> Result<VotingTemplate, ErrorResponse> {
+    match user.key_hash.to_string().eq(&template.admin_key_hash) {
+        true => Ok(template),
+        false => Err(ErrorResponse {
+            reason: format!(
+                "Admin key is not correct for template with name: {}",
+                template.name
+            ),
+            status: Status::Unauthorized,
+        }),
+    }
+}
+
+#[inline(always)]
+pub fn check_if_recovery_secret(
+    voting: Voting,
+    user: &AuthenticatedUser,
+) -> Result<Voting, ErrorResponse> {
+    match voting.recovery_secret_hash.as_ref() == Some(&user.key_hash) {
         true => Ok(voting),
         false => Err(ErrorResponse {
-            reason: format!("Admin key is not correct for voting with id: {}", voting.id),
+            reason: format!(
+                "Recovery secret is not correct for voting with id: {}",
+                voting.id
+            ),
             status: Status::Unauthorized,
         }),
     }
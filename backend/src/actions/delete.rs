@@ -0,0 +1,122 @@
+use crate::models::*;
+use crate::pool::DbConn;
+use crate::utils::ErrorResponse;
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use rocket::http::Status;
+
+pub fn delete_proxy(conn: &DbConn, proxy: &Proxy) -> Result<(), ErrorResponse> {
+    diesel::delete(proxy).execute(&**conn).map_err(|err| {
+        let error_msg = format!(
+            "Could not delete proxy of voter with id: {}",
+            &proxy.voter_fk
+        );
+        println!("{}. err: {:?}", error_msg, err);
+        ErrorResponse {
+            reason: error_msg,
+            status: Status::InternalServerError,
+        }
+    })?;
+
+    Ok(())
+}
+
+pub fn delete_delegation(conn: &DbConn, delegation: &Delegation) -> Result<(), ErrorResponse> {
+    diesel::delete(delegation).execute(&**conn).map_err(|err| {
+        let error_msg = format!(
+            "Could not delete delegation on topic: {} of voter with id: {}",
+            &delegation.topic, &delegation.voter_fk
+        );
+        println!("{}. err: {:?}", error_msg, err);
+        ErrorResponse {
+            reason: error_msg,
+            status: Status::InternalServerError,
+        }
+    })?;
+
+    Ok(())
+}
+
+pub fn delete_poll(conn: &DbConn, poll: &Poll) -> Result<(), ErrorResponse> {
+    diesel::delete(poll).execute(&**conn).map_err(|err| {
+        let error_msg = format!("Could not delete poll with id: {}", &poll.id);
+        println!("{}. err: {:?}", error_msg, err);
+        ErrorResponse {
+            reason: error_msg,
+            status: Status::InternalServerError,
+        }
+    })?;
+
+    Ok(())
+}
+
+/// Polls, voters and everything that belongs to them are deleted by the
+/// `ON DELETE CASCADE` foreign keys.
+pub fn delete_voting(conn: &DbConn, voting: &Voting) -> Result<(), ErrorResponse> {
+    diesel::delete(voting).execute(&**conn).map_err(|err| {
+        let error_msg = format!("Could not delete voting with id: {}", &voting.id);
+        println!("{}. err: {:?}", error_msg, err);
+        ErrorResponse {
+            reason: error_msg,
+            status: Status::InternalServerError,
+        }
+    })?;
+
+    Ok(())
+}
+
+pub fn delete_votings_archived_before(
+    conn: &DbConn,
+    archived_before: NaiveDateTime,
+) -> Result<usize, ErrorResponse> {
+    use crate::schema::votings;
+
+    diesel::delete(votings::table.filter(votings::archived_at.lt(archived_before)))
+        .execute(&**conn)
+        .map_err(|err| {
+            let error_msg = format!(
+                "Could not delete votings archived before: {}",
+                archived_before
+            );
+            println!("{}. err: {:?}", error_msg, err);
+            ErrorResponse {
+                reason: error_msg,
+                status: Status::InternalServerError,
+            }
+        })
+}
+
+pub fn delete_voting_template(
+    conn: &DbConn,
+    template: &VotingTemplate,
+) -> Result<(), ErrorResponse> {
+    diesel::delete(template).execute(&**conn).map_err(|err| {
+        let error_msg = format!("Could not delete template with name: {}", &template.name);
+        println!("{}. err: {:?}", error_msg, err);
+        ErrorResponse {
+            reason: error_msg,
+            status: Status::InternalServerError,
+        }
+    })?;
+
+    Ok(())
+}
+
+pub fn delete_admin_key(conn: &DbConn, admin_key: &AdminKey) -> Result<(), ErrorResponse> {
+    diesel::delete(admin_key).execute(&**conn).map_err(|err| {
+        let error_msg = format!("Could not delete admin key with id: {}", &admin_key.id);
+        println!("{}. err: {:?}", error_msg, err);
+        ErrorResponse {
+            reason: error_msg,
+            status: Status::InternalServerError,
+        }
+    })?;
+
+    Ok(())
+}
+
+pub fn delete_admin_keys(conn: &DbConn, voting: &Voting) -> QueryResult<usize> {
+    use crate::schema::admin_keys;
+
+    diesel::delete(admin_keys::table.filter(admin_keys::voting_fk.eq(&voting.id))).execute(&**conn)
+}
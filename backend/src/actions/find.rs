@@ -1,6 +1,8 @@
 use crate::models::*;
 use crate::pool::DbConn;
+use crate::types::{PollKey, PollState};
 use crate::utils::{AuthenticatedUser, ErrorResponse};
+use chrono::NaiveDateTime;
 use diesel::prelude::*;
 use rocket::http::Status;
 
@@ -22,12 +24,32 @@ pub fn find_amount_of_polls(conn: &DbConn, voting: &Voting) -> Result<i32, Error
         .map(|polls_count| polls_count as i32)
 }
 
+/// The position of the poll in the agenda.
+pub fn find_poll_index(conn: &DbConn, poll: &Poll) -> Result<i32, ErrorResponse> {
+    use crate::schema::polls;
+    use diesel::dsl::count;
+
+    polls::table
+        .filter(polls::voting_fk.eq(&poll.voting_fk))
+        .filter(polls::sequenz_number.lt(poll.sequenz_number))
+        .select(count(polls::id))
+        .first::<i64>(&**conn)
+        .map_err(|_| ErrorResponse {
+            reason: format!("Could not load the index of poll with id: {}", &poll.id),
+            status: Status::InternalServerError,
+        })
+        .map(|polls_count| polls_count as i32)
+}
+
+/// Revoked voters can not vote anymore and do not count towards the voters of
+/// the voting.
 pub fn find_amount_of_voters(conn: &DbConn, voting_id: &String) -> Result<i32, ErrorResponse> {
     use crate::schema::voters;
     use diesel::dsl::count;
 
     voters::table
         .filter(voters::voting_fk.eq(&voting_id))
+        .filter(voters::revoked_at.is_null())
         .select(count(voters::id))
         .first::<i64>(&**conn)
         .map_err(|_| ErrorResponse {
@@ -40,6 +62,24 @@ pub fn find_amount_of_voters(conn: &DbConn, voting_id: &String) -> Result<i32, E
         .map(|voters_count| voters_count as i32)
 }
 
+pub fn find_total_voter_weight(conn: &DbConn, voting_id: &String) -> Result<i64, ErrorResponse> {
+    use crate::schema::voters;
+
+    voters::table
+        .filter(voters::voting_fk.eq(&voting_id))
+        .filter(voters::revoked_at.is_null())
+        .select(voters::weight)
+        .load::<i32>(&**conn)
+        .map_err(|_| ErrorResponse {
+            reason: format!(
+                "Could not load the weight of the voters for voting with id: {}",
+                &voting_id
+            ),
+            status: Status::InternalServerError,
+        })
+        .map(|weights| weights.into_iter().map(|weight| weight as i64).sum())
+}
+
 pub fn find_voter(conn: &DbConn, user: &AuthenticatedUser) -> Result<Voter, ErrorResponse> {
     use crate::schema::voters;
 
@@ -69,6 +109,13 @@ pub fn find_poll_at_index(
 ) -> Result<Poll, ErrorResponse> {
     use crate::schema::polls;
 
+    if index < 0 {
+        return Err(ErrorResponse {
+            reason: format!("Poll index: {} must not be negative", index),
+            status: Status::BadRequest,
+        });
+    }
+
     polls::table
         .filter(polls::voting_fk.eq(&voting.id))
         .order(polls::sequenz_number.asc())
@@ -96,6 +143,46 @@ pub fn find_poll_at_index(
         })
 }
 
+pub fn find_poll(conn: &DbConn, voting: &Voting, poll_id: &String) -> Result<Poll, ErrorResponse> {
+    use crate::schema::polls;
+
+    polls::table
+        .filter(polls::voting_fk.eq(&voting.id))
+        .filter(polls::id.eq(&poll_id))
+        .first::<Poll>(&**conn)
+        .map_err(|err| match err {
+            diesel::NotFound => ErrorResponse {
+                reason: format!(
+                    "Poll with id: {} for voting with id: {} not found",
+                    poll_id, &voting.id
+                ),
+                status: Status::NotFound,
+            },
+            err => {
+                let error_msg = format!(
+                    "Could not query database for poll with id: {} for voting with id: {}",
+                    poll_id, &voting.id
+                );
+                println!("{}. err: {:?}", error_msg, err);
+                ErrorResponse {
+                    reason: error_msg,
+                    status: Status::InternalServerError,
+                }
+            }
+        })
+}
+
+pub fn find_poll_by_key(
+    conn: &DbConn,
+    voting: &Voting,
+    poll_key: &PollKey,
+) -> Result<Poll, ErrorResponse> {
+    match poll_key {
+        PollKey::Index(index) => find_poll_at_index(conn, voting, *index),
+        PollKey::Id(poll_id) => find_poll(conn, voting, poll_id),
+    }
+}
+
 pub fn find_polls(conn: &DbConn, voting_id: &String) -> Result<Vec<Poll>, ErrorResponse> {
     use crate::schema::polls;
 
@@ -109,6 +196,41 @@ pub fn find_polls(conn: &DbConn, voting_id: &String) -> Result<Vec<Poll>, ErrorR
         })
 }
 
+pub fn find_poll_options(
+    conn: &DbConn,
+    poll_id: &String,
+) -> Result<Vec<PollOption>, ErrorResponse> {
+    use crate::schema::poll_options;
+
+    poll_options::table
+        .filter(poll_options::poll_fk.eq(&poll_id))
+        .order(poll_options::sequenz_number.asc())
+        .load::<PollOption>(&**conn)
+        .map_err(|_| ErrorResponse {
+            reason: format!("Could not load options to poll with id: {}", &poll_id),
+            status: Status::InternalServerError,
+        })
+}
+
+pub fn find_poll_option_results(
+    conn: &DbConn,
+    voting_id: &String,
+) -> Result<Vec<PollOptionResult>, ErrorResponse> {
+    use crate::schema_custom::poll_option_results;
+
+    poll_option_results::table
+        .filter(poll_option_results::voting_fk.eq(&voting_id))
+        .order(poll_option_results::sequenz_number.asc())
+        .load::<PollOptionResult>(&**conn)
+        .map_err(|_| ErrorResponse {
+            reason: format!(
+                "Could not load poll options to voting with id: {}",
+                &voting_id
+            ),
+            status: Status::InternalServerError,
+        })
+}
+
 pub fn find_poll_results(
     conn: &DbConn,
     voting_id: &String,
@@ -125,6 +247,47 @@ pub fn find_poll_results(
         })
 }
 
+pub fn find_vote_choices(
+    conn: &DbConn,
+    vote_id: &String,
+) -> Result<Vec<VoteChoice>, ErrorResponse> {
+    use crate::schema::vote_choices;
+
+    vote_choices::table
+        .filter(vote_choices::vote_fk.eq(&vote_id))
+        .order(vote_choices::rank.asc())
+        .load::<VoteChoice>(&**conn)
+        .map_err(|_| ErrorResponse {
+            reason: format!("Could not load choices to vote with id: {}", &vote_id),
+            status: Status::InternalServerError,
+        })
+}
+
+/// The choices of the current ballots, with the voter behind every ballot that
+/// is not secret.
+pub fn find_poll_vote_choices(
+    conn: &DbConn,
+    poll_id: &String,
+) -> Result<Vec<(VoteChoice, Option<NamedVote>)>, ErrorResponse> {
+    use crate::schema::{named_votes, vote_choices, votes};
+
+    vote_choices::table
+        .inner_join(votes::table)
+        .left_join(named_votes::table)
+        .filter(votes::poll_fk.eq(&poll_id))
+        .filter(named_votes::replaced_at.is_null())
+        .order((vote_choices::vote_fk.asc(), vote_choices::rank.asc()))
+        .select((
+            vote_choices::all_columns,
+            named_votes::all_columns.nullable(),
+        ))
+        .load::<(VoteChoice, Option<NamedVote>)>(&**conn)
+        .map_err(|_| ErrorResponse {
+            reason: format!("Could not load vote choices to poll with id: {}", &poll_id),
+            status: Status::InternalServerError,
+        })
+}
+
 pub fn find_voting(conn: &DbConn, voting_id: &String) -> Result<Voting, ErrorResponse> {
     use crate::schema::votings;
 
@@ -148,20 +311,105 @@ pub fn find_voting(conn: &DbConn, voting_id: &String) -> Result<Voting, ErrorRes
         })
 }
 
+pub fn find_participation(
+    conn: &DbConn,
+    poll_id: &String,
+    voter_id: &String,
+) -> Result<Option<Participation>, ErrorResponse> {
+    use crate::schema::participations;
+
+    participations::table
+        .filter(
+            participations::poll_fk
+                .eq(&poll_id)
+                .and(participations::voter_fk.eq(&voter_id)),
+        )
+        .first::<Participation>(&**conn)
+        .optional()
+        .map_err(|err| {
+            let error_msg = format!(
+                "Could not query database for participation with poll_id: {} and voter_id: {}",
+                poll_id, voter_id
+            );
+            println!("{}. err: {:?}", error_msg, err);
+            ErrorResponse {
+                reason: error_msg,
+                status: Status::InternalServerError,
+            }
+        })
+}
+
+pub fn find_voting_participations(
+    conn: &DbConn,
+    voting_id: &String,
+) -> Result<Vec<Participation>, ErrorResponse> {
+    use crate::schema::{participations, polls};
+
+    participations::table
+        .inner_join(polls::table)
+        .filter(polls::voting_fk.eq(&voting_id))
+        .select(participations::all_columns)
+        .load::<Participation>(&**conn)
+        .map_err(|_| ErrorResponse {
+            reason: format!(
+                "Could not load participations to voting with id: {}",
+                &voting_id
+            ),
+            status: Status::InternalServerError,
+        })
+}
+
+/// Votings with a draft poll that is due to open or an open poll that is due
+/// to close, and votings with an auto-advancing agenda.
+pub fn find_votings_with_due_polls(
+    conn: &DbConn,
+    now: NaiveDateTime,
+) -> Result<Vec<Voting>, ErrorResponse> {
+    use crate::schema::{polls, votings};
+
+    votings::table
+        .filter(
+            votings::id
+                .eq_any(
+                    polls::table
+                        .filter(
+                            polls::state
+                                .eq(PollState::Draft)
+                                .and(polls::opens_at.le(now))
+                                .or(polls::state
+                                    .eq(PollState::Open)
+                                    .and(polls::closes_at.le(now))),
+                        )
+                        .select(polls::voting_fk),
+                )
+                .or(votings::auto_advance
+                    .eq(true)
+                    .and(votings::agenda_poll_index.is_not_null())),
+        )
+        .filter(votings::archived_at.is_null())
+        .load::<Voting>(&**conn)
+        .map_err(|_| ErrorResponse {
+            reason: "Could not load votings with due polls".to_string(),
+            status: Status::InternalServerError,
+        })
+}
+
+/// The current ballot of the voter. Always `None` in secret votings.
 pub fn find_vote(
     conn: &DbConn,
     poll_id: &String,
     voter_id: &String,
-) -> Result<Option<Vote>, ErrorResponse> {
-    use crate::schema::votes;
+) -> Result<Option<NamedVote>, ErrorResponse> {
+    use crate::schema::named_votes;
 
-    votes::table
+    named_votes::table
         .filter(
-            votes::poll_fk
+            named_votes::poll_fk
                 .eq(&poll_id)
-                .and(votes::voter_fk.eq(&voter_id)),
+                .and(named_votes::voter_fk.eq(&voter_id))
+                .and(named_votes::replaced_at.is_null()),
         )
-        .first::<Vote>(&**conn)
+        .first::<NamedVote>(&**conn)
         .optional()
         .map_err(|err| {
             let error_msg = format!(
@@ -175,3 +423,433 @@ pub fn find_vote(
             }
         })
 }
+
+/// The current ballot of the voter, locked until the transaction ends.
+pub fn find_vote_for_update(
+    conn: &DbConn,
+    poll_id: &String,
+    voter_id: &String,
+) -> QueryResult<Option<NamedVote>> {
+    use crate::schema::named_votes;
+
+    named_votes::table
+        .filter(
+            named_votes::poll_fk
+                .eq(&poll_id)
+                .and(named_votes::voter_fk.eq(&voter_id))
+                .and(named_votes::replaced_at.is_null()),
+        )
+        .for_update()
+        .first::<NamedVote>(&**conn)
+        .optional()
+}
+
+pub fn find_amount_of_participations(
+    conn: &DbConn,
+    poll_id: &String,
+) -> Result<i32, ErrorResponse> {
+    use crate::schema::participations;
+    use diesel::dsl::count;
+
+    participations::table
+        .filter(participations::poll_fk.eq(&poll_id))
+        .select(count(participations::id))
+        .first::<i64>(&**conn)
+        .map_err(|_| ErrorResponse {
+            reason: format!(
+                "Could not load the amount of participations for poll with id: {}",
+                &poll_id
+            ),
+            status: Status::InternalServerError,
+        })
+        .map(|participations_count| participations_count as i32)
+}
+
+/// How often the voter replaced their ballot on the poll.
+pub fn find_amount_of_vote_changes(
+    conn: &DbConn,
+    poll_id: &String,
+    voter_id: &String,
+) -> Result<i32, ErrorResponse> {
+    use crate::schema::named_votes;
+    use diesel::dsl::count;
+
+    named_votes::table
+        .filter(
+            named_votes::poll_fk
+                .eq(&poll_id)
+                .and(named_votes::voter_fk.eq(&voter_id))
+                .and(named_votes::replaced_at.is_not_null()),
+        )
+        .select(count(named_votes::vote_fk))
+        .first::<i64>(&**conn)
+        .map_err(|_| ErrorResponse {
+            reason: format!(
+                "Could not load the amount of vote changes for poll with id: {}",
+                &poll_id
+            ),
+            status: Status::InternalServerError,
+        })
+        .map(|changes_count| changes_count as i32)
+}
+
+pub fn find_voter_by_id(conn: &DbConn, voter_id: &String) -> Result<Voter, ErrorResponse> {
+    use crate::schema::voters;
+
+    voters::table
+        .find(&voter_id)
+        .first::<Voter>(&**conn)
+        .map_err(|err| {
+            let error_msg = format!("Could not query database for voter with id: {}", voter_id);
+            println!("{}. err: {:?}", error_msg, err);
+            ErrorResponse {
+                reason: error_msg,
+                status: Status::InternalServerError,
+            }
+        })
+}
+
+pub fn find_voting_voter(
+    conn: &DbConn,
+    voting: &Voting,
+    voter_id: &String,
+) -> Result<Voter, ErrorResponse> {
+    use crate::schema::voters;
+
+    voters::table
+        .filter(
+            voters::id
+                .eq(&voter_id)
+                .and(voters::voting_fk.eq(&voting.id)),
+        )
+        .first::<Voter>(&**conn)
+        .map_err(|err| match err {
+            diesel::NotFound => ErrorResponse {
+                reason: format!(
+                    "Voter with id: {} not found in voting with id: {}",
+                    voter_id, &voting.id
+                ),
+                status: Status::NotFound,
+            },
+            err => {
+                let error_msg = format!(
+                    "Could not query database for voter with id: {} in voting with id: {}",
+                    voter_id, &voting.id
+                );
+                println!("{}. err: {:?}", error_msg, err);
+                ErrorResponse {
+                    reason: error_msg,
+                    status: Status::InternalServerError,
+                }
+            }
+        })
+}
+
+pub fn find_voters(conn: &DbConn, voting_id: &String) -> Result<Vec<Voter>, ErrorResponse> {
+    use crate::schema::voters;
+
+    voters::table
+        .filter(voters::voting_fk.eq(&voting_id))
+        .order(voters::username.asc())
+        .load::<Voter>(&**conn)
+        .map_err(|_| ErrorResponse {
+            reason: format!("Could not load voters to voting with id: {}", &voting_id),
+            status: Status::InternalServerError,
+        })
+}
+
+/// Voters who were checked in at the given time, without revoked voters.
+pub fn find_present_voters(
+    conn: &DbConn,
+    voting_id: &String,
+    at: NaiveDateTime,
+) -> Result<Vec<Voter>, ErrorResponse> {
+    use crate::schema::{attendances, voters};
+
+    voters::table
+        .inner_join(attendances::table)
+        .filter(voters::voting_fk.eq(&voting_id))
+        .filter(voters::revoked_at.is_null())
+        .filter(attendances::checked_in_at.le(at))
+        .filter(
+            attendances::checked_out_at
+                .is_null()
+                .or(attendances::checked_out_at.gt(at)),
+        )
+        .select(voters::all_columns)
+        .distinct()
+        .load::<Voter>(&**conn)
+        .map_err(|_| ErrorResponse {
+            reason: format!(
+                "Could not load present voters to voting with id: {}",
+                &voting_id
+            ),
+            status: Status::InternalServerError,
+        })
+}
+
+/// The voters who can vote on a poll of present voters only that opened at
+/// `opened_at`. A ballot given to a proxy is cast by the proxy holder, so it
+/// counts when the proxy holder was present, whether its voter was or not.
+pub fn find_eligible_voters(
+    conn: &DbConn,
+    voting_id: &String,
+    opened_at: NaiveDateTime,
+) -> Result<Vec<Voter>, ErrorResponse> {
+    let present_voters = find_present_voters(conn, voting_id, opened_at)?;
+    let proxies = find_proxies(conn, voting_id)?;
+
+    Ok(find_voters(conn, voting_id)?
+        .into_iter()
+        .filter(|voter| {
+            let casting_voter_id = proxies
+                .iter()
+                .find(|proxy| proxy.voter_fk == voter.id)
+                .map_or(&voter.id, |proxy| &proxy.proxy_voter_fk);
+            voter.revoked_at.is_none()
+                && present_voters
+                    .iter()
+                    .any(|present_voter| &present_voter.id == casting_voter_id)
+        })
+        .collect())
+}
+
+/// The attendance of the voter that is not checked out yet.
+pub fn find_current_attendance(
+    conn: &DbConn,
+    voter_id: &String,
+) -> Result<Option<Attendance>, ErrorResponse> {
+    use crate::schema::attendances;
+
+    attendances::table
+        .filter(attendances::voter_fk.eq(&voter_id))
+        .filter(attendances::checked_out_at.is_null())
+        .first::<Attendance>(&**conn)
+        .optional()
+        .map_err(|err| {
+            let error_msg = format!(
+                "Could not query database for attendance of voter with id: {}",
+                voter_id
+            );
+            println!("{}. err: {:?}", error_msg, err);
+            ErrorResponse {
+                reason: error_msg,
+                status: Status::InternalServerError,
+            }
+        })
+}
+
+pub fn find_attendances(
+    conn: &DbConn,
+    voting_id: &String,
+) -> Result<Vec<Attendance>, ErrorResponse> {
+    use crate::schema::attendances;
+
+    attendances::table
+        .filter(attendances::voting_fk.eq(&voting_id))
+        .order(attendances::checked_in_at.asc())
+        .load::<Attendance>(&**conn)
+        .map_err(|_| ErrorResponse {
+            reason: format!(
+                "Could not load attendances to voting with id: {}",
+                &voting_id
+            ),
+            status: Status::InternalServerError,
+        })
+}
+
+/// The proxy holding the ballot of the voter, if they gave it away.
+pub fn find_proxy(conn: &DbConn, voter_id: &String) -> Result<Option<Proxy>, ErrorResponse> {
+    use crate::schema::proxies;
+
+    proxies::table
+        .filter(proxies::voter_fk.eq(&voter_id))
+        .first::<Proxy>(&**conn)
+        .optional()
+        .map_err(|err| {
+            let error_msg = format!(
+                "Could not query database for proxy of voter with id: {}",
+                voter_id
+            );
+            println!("{}. err: {:?}", error_msg, err);
+            ErrorResponse {
+                reason: error_msg,
+                status: Status::InternalServerError,
+            }
+        })
+}
+
+/// The proxies of the ballots the voter holds for other voters.
+pub fn find_held_proxies(
+    conn: &DbConn,
+    proxy_voter_id: &String,
+) -> Result<Vec<Proxy>, ErrorResponse> {
+    use crate::schema::proxies;
+
+    proxies::table
+        .filter(proxies::proxy_voter_fk.eq(&proxy_voter_id))
+        .load::<Proxy>(&**conn)
+        .map_err(|_| ErrorResponse {
+            reason: format!(
+                "Could not load proxies held by voter with id: {}",
+                &proxy_voter_id
+            ),
+            status: Status::InternalServerError,
+        })
+}
+
+pub fn find_proxies(conn: &DbConn, voting_id: &String) -> Result<Vec<Proxy>, ErrorResponse> {
+    use crate::schema::proxies;
+
+    proxies::table
+        .filter(proxies::voting_fk.eq(&voting_id))
+        .load::<Proxy>(&**conn)
+        .map_err(|_| ErrorResponse {
+            reason: format!("Could not load proxies to voting with id: {}", &voting_id),
+            status: Status::InternalServerError,
+        })
+}
+
+pub fn find_delegations(
+    conn: &DbConn,
+    voting_id: &String,
+    topic: &String,
+) -> Result<Vec<Delegation>, ErrorResponse> {
+    use crate::schema::delegations;
+
+    delegations::table
+        .filter(
+            delegations::voting_fk
+                .eq(&voting_id)
+                .and(delegations::topic.eq(&topic)),
+        )
+        .order(delegations::voter_fk.asc())
+        .load::<Delegation>(&**conn)
+        .map_err(|_| ErrorResponse {
+            reason: format!(
+                "Could not load delegations on topic: {} to voting with id: {}",
+                topic, &voting_id
+            ),
+            status: Status::InternalServerError,
+        })
+}
+
+pub fn find_voter_delegations(
+    conn: &DbConn,
+    voter_id: &String,
+) -> Result<Vec<Delegation>, ErrorResponse> {
+    use crate::schema::delegations;
+
+    delegations::table
+        .filter(delegations::voter_fk.eq(&voter_id))
+        .order(delegations::topic.asc())
+        .load::<Delegation>(&**conn)
+        .map_err(|_| ErrorResponse {
+            reason: format!("Could not load delegations of voter with id: {}", &voter_id),
+            status: Status::InternalServerError,
+        })
+}
+
+pub fn find_voting_template(conn: &DbConn, name: &String) -> Result<VotingTemplate, ErrorResponse> {
+    use crate::schema::voting_templates;
+
+    voting_templates::table
+        .filter(voting_templates::name.eq(&name))
+        .first::<VotingTemplate>(&**conn)
+        .map_err(|err| match err {
+            diesel::NotFound => ErrorResponse {
+                reason: format!("Template with name: {} not found", name),
+                status: Status::NotFound,
+            },
+            err => {
+                let error_msg =
+                    format!("Could not query database for template with name: {}", name);
+                println!("{}. err: {:?}", error_msg, err);
+                ErrorResponse {
+                    reason: error_msg,
+                    status: Status::InternalServerError,
+                }
+            }
+        })
+}
+
+/// The admin key of the voting the user authenticated with, if any.
+pub fn find_admin_key(
+    conn: &DbConn,
+    voting_id: &String,
+    user: &AuthenticatedUser,
+) -> Result<Option<AdminKey>, ErrorResponse> {
+    use crate::schema::admin_keys;
+
+    admin_keys::table
+        .filter(
+            admin_keys::voting_fk
+                .eq(&voting_id)
+                .and(admin_keys::key_hash.eq(&user.key_hash)),
+        )
+        .first::<AdminKey>(&**conn)
+        .optional()
+        .map_err(|err| {
+            let error_msg = format!(
+                "Could not query database for admin key of voting with id: {}",
+                voting_id
+            );
+            println!("{}. err: {:?}", error_msg, err);
+            ErrorResponse {
+                reason: error_msg,
+                status: Status::InternalServerError,
+            }
+        })
+}
+
+pub fn find_voting_admin_key(
+    conn: &DbConn,
+    voting: &Voting,
+    admin_key_id: &String,
+) -> Result<AdminKey, ErrorResponse> {
+    use crate::schema::admin_keys;
+
+    admin_keys::table
+        .filter(
+            admin_keys::id
+                .eq(&admin_key_id)
+                .and(admin_keys::voting_fk.eq(&voting.id)),
+        )
+        .first::<AdminKey>(&**conn)
+        .map_err(|err| match err {
+            diesel::NotFound => ErrorResponse {
+                reason: format!(
+                    "Admin key with id: {} not found in voting with id: {}",
+                    admin_key_id, &voting.id
+                ),
+                status: Status::NotFound,
+            },
+            err => {
+                let error_msg = format!(
+                    "Could not query database for admin key with id: {} in voting with id: {}",
+                    admin_key_id, &voting.id
+                );
+                println!("{}. err: {:?}", error_msg, err);
+                ErrorResponse {
+                    reason: error_msg,
+                    status: Status::InternalServerError,
+                }
+            }
+        })
+}
+
+pub fn find_admin_keys(conn: &DbConn, voting_id: &String) -> Result<Vec<AdminKey>, ErrorResponse> {
+    use crate::schema::admin_keys;
+
+    admin_keys::table
+        .filter(admin_keys::voting_fk.eq(&voting_id))
+        .order(admin_keys::created_at.asc())
+        .load::<AdminKey>(&**conn)
+        .map_err(|_| ErrorResponse {
+            reason: format!(
+                "Could not load admin keys to voting with id: {}",
+                &voting_id
+            ),
+            status: Status::InternalServerError,
+        })
+}
@@ -1,26 +1,39 @@
 use crate::pool::DbConn;
 
+use crate::models::{NewPoll, NewVoting, Voter};
+use crate::types::{AdminScope, PresetAnswer};
 use crate::utils::ErrorResponse;
 
+use chrono::NaiveDateTime;
 use diesel::insert_into;
 use diesel::prelude::*;
+use diesel::result::{DatabaseErrorKind, Error::DatabaseError};
 use rocket::http::Status;
 
-pub fn insert_poll(
+pub fn insert_poll(conn: &DbConn, poll: &NewPoll) -> QueryResult<String> {
+    use crate::schema::polls;
+
+    insert_into(polls::table)
+        .values(poll)
+        .returning(polls::id)
+        .get_result(&**conn)
+}
+
+pub fn insert_poll_option(
     conn: &DbConn,
-    name: &String,
+    name: &str,
     sequenz_number: i32,
-    description: &String,
-    voting_fk: &String,
+    poll_fk: &String,
+    preset_answer: Option<PresetAnswer>,
 ) -> QueryResult<usize> {
-    use crate::schema::polls;
+    use crate::schema::poll_options;
 
-    insert_into(polls::table)
+    insert_into(poll_options::table)
         .values((
-            polls::name.eq(&name),
-            polls::sequenz_number.eq(sequenz_number),
-            polls::description.eq(&description),
-            polls::voting_fk.eq(&voting_fk),
+            poll_options::name.eq(name),
+            poll_options::sequenz_number.eq(sequenz_number),
+            poll_options::poll_fk.eq(&poll_fk),
+            poll_options::preset_answer.eq(preset_answer),
         ))
         .execute(&**conn)
 }
@@ -30,7 +43,9 @@ pub fn insert_voter(
     username: &String,
     voter_key_hash: &String,
     voting_id: &String,
-) -> Result<(), ErrorResponse> {
+    weight: i32,
+    email: Option<&String>,
+) -> QueryResult<String> {
     use crate::schema::voters;
 
     insert_into(voters::table)
@@ -38,38 +53,154 @@ pub fn insert_voter(
             voters::username.eq(&username),
             voters::voter_key_hash.eq(&voter_key_hash),
             voters::voting_fk.eq(&voting_id),
+            voters::weight.eq(weight),
+            voters::email.eq(email),
+        ))
+        .returning(voters::id)
+        .get_result(&**conn)
+}
+
+pub fn insert_participation(
+    conn: &DbConn,
+    poll_id: &String,
+    voter_id: &String,
+    proxy_voter_id: Option<&String>,
+) -> QueryResult<usize> {
+    use crate::schema::participations;
+
+    insert_into(participations::table)
+        .values((
+            participations::poll_fk.eq(&poll_id),
+            participations::voter_fk.eq(&voter_id),
+            participations::proxy_voter_fk.eq(proxy_voter_id),
+        ))
+        .execute(&**conn)
+}
+
+pub fn insert_vote(conn: &DbConn, poll_id: &String) -> QueryResult<String> {
+    use crate::schema::votes;
+
+    insert_into(votes::table)
+        .values(votes::poll_fk.eq(&poll_id))
+        .returning(votes::id)
+        .get_result(&**conn)
+}
+
+/// Links a ballot to its voter. Never called for secret ballots.
+pub fn insert_named_vote(
+    conn: &DbConn,
+    vote_id: &String,
+    poll_id: &String,
+    voter: &Voter,
+    voted_at: NaiveDateTime,
+) -> QueryResult<usize> {
+    use crate::schema::named_votes;
+
+    insert_into(named_votes::table)
+        .values((
+            named_votes::vote_fk.eq(&vote_id),
+            named_votes::poll_fk.eq(&poll_id),
+            named_votes::voter_fk.eq(&voter.id),
+            named_votes::weight.eq(voter.weight),
+            named_votes::voted_at.eq(voted_at),
+        ))
+        .execute(&**conn)
+}
+
+pub fn insert_vote_choice(
+    conn: &DbConn,
+    vote_id: &String,
+    poll_option_id: &String,
+    rank: i32,
+    score: Option<i32>,
+) -> QueryResult<usize> {
+    use crate::schema::vote_choices;
+
+    insert_into(vote_choices::table)
+        .values((
+            vote_choices::vote_fk.eq(&vote_id),
+            vote_choices::poll_option_fk.eq(&poll_option_id),
+            vote_choices::rank.eq(rank),
+            vote_choices::score.eq(score),
+        ))
+        .execute(&**conn)
+}
+
+pub fn insert_voting(conn: &DbConn, voting: &NewVoting) -> QueryResult<String> {
+    use crate::schema::votings;
+
+    insert_into(votings::table)
+        .values(voting)
+        .returning(votings::id)
+        .get_result(&**conn)
+}
+
+pub fn insert_admin_key(
+    conn: &DbConn,
+    voting_id: &String,
+    key_hash: &String,
+    label: &String,
+    scope: AdminScope,
+) -> QueryResult<String> {
+    use crate::schema::admin_keys;
+
+    insert_into(admin_keys::table)
+        .values((
+            admin_keys::voting_fk.eq(&voting_id),
+            admin_keys::key_hash.eq(&key_hash),
+            admin_keys::label.eq(&label),
+            admin_keys::scope.eq(scope),
+        ))
+        .returning(admin_keys::id)
+        .get_result(&**conn)
+}
+
+pub fn insert_attendance(
+    conn: &DbConn,
+    voting_id: &String,
+    voter_id: &String,
+    checked_in_at: NaiveDateTime,
+) -> Result<(), ErrorResponse> {
+    use crate::schema::attendances;
+
+    insert_into(attendances::table)
+        .values((
+            attendances::voting_fk.eq(&voting_id),
+            attendances::voter_fk.eq(&voter_id),
+            attendances::checked_in_at.eq(checked_in_at),
         ))
         .execute(&**conn)
         .map_err(|err| {
-            let error_msg = format!("Could not insert voter for voting with id: {}", voting_id);
+            let error_msg = format!("Could not check in voter with id: {}", voter_id);
             println!("{}. err: {:?}", error_msg, err);
             ErrorResponse {
                 reason: error_msg,
                 status: Status::InternalServerError,
             }
         })?;
+
     Ok(())
 }
 
-pub fn insert_vote(
+pub fn insert_proxy(
     conn: &DbConn,
-    poll_id: &String,
+    voting_id: &String,
     voter_id: &String,
-    answer: &Option<bool>,
+    proxy_voter_id: &String,
 ) -> Result<(), ErrorResponse> {
-    use crate::schema::votes;
+    use crate::schema::proxies;
 
-    insert_into(votes::table)
+    insert_into(proxies::table)
         .values((
-            votes::poll_fk.eq(&poll_id),
-            votes::voter_fk.eq(&voter_id),
-            votes::answer.eq(answer),
+            proxies::voting_fk.eq(&voting_id),
+            proxies::voter_fk.eq(&voter_id),
+            proxies::proxy_voter_fk.eq(&proxy_voter_id),
         ))
         .execute(&**conn)
         .map_err(|err| {
             let error_msg = format!(
-                "Could not insert vote for poll id: {} and voter id: {} with answer: {:?}",
-                poll_id, voter_id, answer
+                "Could not insert proxy: {} for voter with id: {}",
+                proxy_voter_id, voter_id
             );
             println!("{}. err: {:?}", error_msg, err);
             ErrorResponse {
@@ -77,18 +208,74 @@ pub fn insert_vote(
                 status: Status::InternalServerError,
             }
         })?;
+    Ok(())
+}
+
+/// Inserts the delegation or replaces the delegate of an existing delegation
+/// of the voter on the same topic.
+pub fn insert_delegation(
+    conn: &DbConn,
+    voting_id: &String,
+    voter_id: &String,
+    delegate_voter_id: &String,
+    topic: &String,
+) -> Result<(), ErrorResponse> {
+    use crate::schema::delegations;
 
+    insert_into(delegations::table)
+        .values((
+            delegations::voting_fk.eq(&voting_id),
+            delegations::voter_fk.eq(&voter_id),
+            delegations::delegate_voter_fk.eq(&delegate_voter_id),
+            delegations::topic.eq(&topic),
+        ))
+        .on_conflict((delegations::voter_fk, delegations::topic))
+        .do_update()
+        .set(delegations::delegate_voter_fk.eq(&delegate_voter_id))
+        .execute(&**conn)
+        .map_err(|err| {
+            let error_msg = format!(
+                "Could not insert delegation on topic: {} for voter with id: {}",
+                topic, voter_id
+            );
+            println!("{}. err: {:?}", error_msg, err);
+            ErrorResponse {
+                reason: error_msg,
+                status: Status::InternalServerError,
+            }
+        })?;
     Ok(())
 }
 
-pub fn insert_voting(conn: &DbConn, name: &String, admin_key_hash: &String) -> QueryResult<String> {
-    use crate::schema::votings;
+pub fn insert_voting_template(
+    conn: &DbConn,
+    name: &String,
+    admin_key_hash: &String,
+    definition: &String,
+) -> Result<(), ErrorResponse> {
+    use crate::schema::voting_templates;
 
-    insert_into(votings::table)
+    insert_into(voting_templates::table)
         .values((
-            votings::name.eq(&name),
-            votings::admin_key_hash.eq(&admin_key_hash),
+            voting_templates::name.eq(&name),
+            voting_templates::admin_key_hash.eq(&admin_key_hash),
+            voting_templates::definition.eq(&definition),
         ))
-        .returning(votings::id)
-        .get_result(&**conn)
+        .execute(&**conn)
+        .map_err(|err| match err {
+            DatabaseError(DatabaseErrorKind::UniqueViolation, _) => ErrorResponse {
+                reason: format!("Template with name: {} already exists", name),
+                status: Status::BadRequest,
+            },
+            err => {
+                let error_msg = format!("Could not insert template with name: {}", name);
+                println!("{}. err: {:?}", error_msg, err);
+                ErrorResponse {
+                    reason: error_msg,
+                    status: Status::InternalServerError,
+                }
+            }
+        })?;
+
+    Ok(())
 }
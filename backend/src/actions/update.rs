@@ -1,22 +1,24 @@
 use crate::models::*;
 use crate::pool::DbConn;
+use crate::types::PollState;
 use crate::utils::ErrorResponse;
+use chrono::NaiveDateTime;
 use diesel::prelude::*;
 use rocket::http::Status;
 
-pub fn update_voting_active_poll_index(
+pub fn update_voting_agenda_poll_index(
     conn: &DbConn,
     voting: &Voting,
-    poll_index: &Option<i32>,
+    poll_index: Option<i32>,
 ) -> Result<(), ErrorResponse> {
     use crate::schema::votings;
 
     diesel::update(voting)
-        .set(votings::active_poll_index.eq(poll_index))
+        .set(votings::agenda_poll_index.eq(poll_index))
         .execute(&**conn)
         .map_err(|err| {
             let error_msg = format!(
-                "Could not set active_poll_index: {:?} for voting with id: {}",
+                "Could not set agenda_poll_index: {:?} for voting with id: {}",
                 poll_index, &voting.id
             );
             println!("{}. err: {:?}", error_msg, err);
@@ -28,3 +30,368 @@ pub fn update_voting_active_poll_index(
 
     Ok(())
 }
+
+pub fn update_voting_casting_voter(
+    conn: &DbConn,
+    voting: &Voting,
+    voter_id: &String,
+) -> Result<(), ErrorResponse> {
+    use crate::schema::votings;
+
+    diesel::update(voting)
+        .set(votings::casting_voter_fk.eq(voter_id))
+        .execute(&**conn)
+        .map_err(|err| {
+            let error_msg = format!(
+                "Could not set casting voter: {} for voting with id: {}",
+                voter_id, &voting.id
+            );
+            println!("{}. err: {:?}", error_msg, err);
+            ErrorResponse {
+                reason: error_msg,
+                status: Status::InternalServerError,
+            }
+        })?;
+
+    Ok(())
+}
+
+pub fn update_poll_chair_decision(
+    conn: &DbConn,
+    poll: &Poll,
+    option_id: &String,
+) -> Result<(), ErrorResponse> {
+    use crate::schema::polls;
+
+    diesel::update(poll)
+        .set(polls::chair_decision_option_fk.eq(option_id))
+        .execute(&**conn)
+        .map_err(|err| {
+            let error_msg = format!(
+                "Could not set chair decision: {} for poll with id: {}",
+                option_id, &poll.id
+            );
+            println!("{}. err: {:?}", error_msg, err);
+            ErrorResponse {
+                reason: error_msg,
+                status: Status::InternalServerError,
+            }
+        })?;
+
+    Ok(())
+}
+
+/// Affects no row when the vote was already replaced.
+pub fn update_vote_replaced(
+    conn: &DbConn,
+    vote: &NamedVote,
+    replaced_at: NaiveDateTime,
+) -> QueryResult<usize> {
+    use crate::schema::named_votes;
+
+    diesel::update(
+        named_votes::table
+            .filter(named_votes::vote_fk.eq(&vote.vote_fk))
+            .filter(named_votes::replaced_at.is_null()),
+    )
+    .set(named_votes::replaced_at.eq(replaced_at))
+    .execute(&**conn)
+}
+
+/// Opening a poll starts it again without a `closed_at` and final result.
+pub fn update_poll_state(
+    conn: &DbConn,
+    poll: &Poll,
+    state: PollState,
+    changed_at: NaiveDateTime,
+) -> Result<(), ErrorResponse> {
+    use crate::schema::polls;
+
+    match state {
+        PollState::Draft => diesel::update(poll)
+            .set(polls::state.eq(state))
+            .execute(&**conn),
+        PollState::Open => diesel::update(poll)
+            .set((
+                polls::state.eq(state),
+                polls::opened_at.eq(changed_at),
+                polls::closed_at.eq(None::<NaiveDateTime>),
+                polls::final_status.eq(None::<String>),
+                polls::final_winner_option_fk.eq(None::<String>),
+            ))
+            .execute(&**conn),
+        PollState::Closed => diesel::update(poll)
+            .set((polls::state.eq(state), polls::closed_at.eq(changed_at)))
+            .execute(&**conn),
+    }
+    .map_err(|err| {
+        let error_msg = format!(
+            "Could not set state: {} for poll with id: {}",
+            state.as_str(),
+            &poll.id
+        );
+        println!("{}. err: {:?}", error_msg, err);
+        ErrorResponse {
+            reason: error_msg,
+            status: Status::InternalServerError,
+        }
+    })?;
+
+    Ok(())
+}
+
+pub fn update_poll_final_result(
+    conn: &DbConn,
+    poll: &Poll,
+    status: &String,
+    winner_option_id: &Option<String>,
+) -> Result<(), ErrorResponse> {
+    use crate::schema::polls;
+
+    diesel::update(poll)
+        .set((
+            polls::final_status.eq(status),
+            polls::final_winner_option_fk.eq(winner_option_id),
+        ))
+        .execute(&**conn)
+        .map_err(|err| {
+            let error_msg = format!("Could not set final result for poll with id: {}", &poll.id);
+            println!("{}. err: {:?}", error_msg, err);
+            ErrorResponse {
+                reason: error_msg,
+                status: Status::InternalServerError,
+            }
+        })?;
+
+    Ok(())
+}
+
+pub fn update_poll_schedule(
+    conn: &DbConn,
+    poll: &Poll,
+    opens_at: Option<NaiveDateTime>,
+    closes_at: Option<NaiveDateTime>,
+) -> Result<(), ErrorResponse> {
+    use crate::schema::polls;
+
+    diesel::update(poll)
+        .set((polls::opens_at.eq(opens_at), polls::closes_at.eq(closes_at)))
+        .execute(&**conn)
+        .map_err(|err| {
+            let error_msg = format!("Could not set schedule for poll with id: {}", &poll.id);
+            println!("{}. err: {:?}", error_msg, err);
+            ErrorResponse {
+                reason: error_msg,
+                status: Status::InternalServerError,
+            }
+        })?;
+
+    Ok(())
+}
+
+pub fn update_poll_texts(
+    conn: &DbConn,
+    poll: &Poll,
+    name: &str,
+    description: &str,
+) -> Result<(), ErrorResponse> {
+    use crate::schema::polls;
+
+    diesel::update(poll)
+        .set((polls::name.eq(name), polls::description.eq(description)))
+        .execute(&**conn)
+        .map_err(|err| {
+            let error_msg = format!(
+                "Could not set name and description for poll with id: {}",
+                &poll.id
+            );
+            println!("{}. err: {:?}", error_msg, err);
+            ErrorResponse {
+                reason: error_msg,
+                status: Status::InternalServerError,
+            }
+        })?;
+
+    Ok(())
+}
+
+pub fn update_poll_sequenz_number(
+    conn: &DbConn,
+    poll: &Poll,
+    sequenz_number: i32,
+) -> QueryResult<usize> {
+    use crate::schema::polls;
+
+    diesel::update(poll)
+        .set(polls::sequenz_number.eq(sequenz_number))
+        .execute(&**conn)
+}
+
+pub fn update_voting_archived(
+    conn: &DbConn,
+    voting: &Voting,
+    archived_at: NaiveDateTime,
+) -> Result<(), ErrorResponse> {
+    use crate::schema::votings;
+
+    diesel::update(voting)
+        .set(votings::archived_at.eq(archived_at))
+        .execute(&**conn)
+        .map_err(|err| {
+            let error_msg = format!("Could not archive voting with id: {}", &voting.id);
+            println!("{}. err: {:?}", error_msg, err);
+            ErrorResponse {
+                reason: error_msg,
+                status: Status::InternalServerError,
+            }
+        })?;
+
+    Ok(())
+}
+
+pub fn update_voting_template_definition(
+    conn: &DbConn,
+    template: &VotingTemplate,
+    definition: &String,
+) -> Result<(), ErrorResponse> {
+    use crate::schema::voting_templates;
+
+    diesel::update(template)
+        .set(voting_templates::definition.eq(definition))
+        .execute(&**conn)
+        .map_err(|err| {
+            let error_msg = format!("Could not update template with name: {}", &template.name);
+            println!("{}. err: {:?}", error_msg, err);
+            ErrorResponse {
+                reason: error_msg,
+                status: Status::InternalServerError,
+            }
+        })?;
+
+    Ok(())
+}
+
+pub fn update_voter_username(
+    conn: &DbConn,
+    voter: &Voter,
+    username: &String,
+) -> Result<(), ErrorResponse> {
+    use crate::schema::voters;
+
+    diesel::update(voter)
+        .set(voters::username.eq(username))
+        .execute(&**conn)
+        .map_err(|err| {
+            let error_msg = format!("Could not rename voter with id: {}", &voter.id);
+            println!("{}. err: {:?}", error_msg, err);
+            ErrorResponse {
+                reason: error_msg,
+                status: Status::InternalServerError,
+            }
+        })?;
+
+    Ok(())
+}
+
+pub fn update_voter_revoked(
+    conn: &DbConn,
+    voter: &Voter,
+    revoked_at: NaiveDateTime,
+) -> Result<(), ErrorResponse> {
+    use crate::schema::voters;
+
+    diesel::update(voter)
+        .set(voters::revoked_at.eq(revoked_at))
+        .execute(&**conn)
+        .map_err(|err| {
+            let error_msg = format!("Could not revoke voter with id: {}", &voter.id);
+            println!("{}. err: {:?}", error_msg, err);
+            ErrorResponse {
+                reason: error_msg,
+                status: Status::InternalServerError,
+            }
+        })?;
+
+    Ok(())
+}
+
+pub fn update_voter_key_hash(
+    conn: &DbConn,
+    voter: &Voter,
+    voter_key_hash: &String,
+) -> Result<(), ErrorResponse> {
+    use crate::schema::voters;
+
+    diesel::update(voter)
+        .set(voters::voter_key_hash.eq(voter_key_hash))
+        .execute(&**conn)
+        .map_err(|err| {
+            let error_msg = format!("Could not replace the key of voter with id: {}", &voter.id);
+            println!("{}. err: {:?}", error_msg, err);
+            ErrorResponse {
+                reason: error_msg,
+                status: Status::InternalServerError,
+            }
+        })?;
+
+    Ok(())
+}
+
+pub fn update_attendance_checked_out(
+    conn: &DbConn,
+    attendance: &Attendance,
+    checked_out_at: NaiveDateTime,
+) -> Result<(), ErrorResponse> {
+    use crate::schema::attendances;
+
+    diesel::update(attendance)
+        .set(attendances::checked_out_at.eq(checked_out_at))
+        .execute(&**conn)
+        .map_err(|err| {
+            let error_msg = format!(
+                "Could not check out voter with id: {}",
+                &attendance.voter_fk
+            );
+            println!("{}. err: {:?}", error_msg, err);
+            ErrorResponse {
+                reason: error_msg,
+                status: Status::InternalServerError,
+            }
+        })?;
+
+    Ok(())
+}
+
+pub fn update_admin_key_hash(
+    conn: &DbConn,
+    admin_key: &AdminKey,
+    key_hash: &String,
+) -> Result<(), ErrorResponse> {
+    use crate::schema::admin_keys;
+
+    diesel::update(admin_key)
+        .set(admin_keys::key_hash.eq(key_hash))
+        .execute(&**conn)
+        .map_err(|err| {
+            let error_msg = format!("Could not rotate admin key with id: {}", &admin_key.id);
+            println!("{}. err: {:?}", error_msg, err);
+            ErrorResponse {
+                reason: error_msg,
+                status: Status::InternalServerError,
+            }
+        })?;
+
+    Ok(())
+}
+
+pub fn update_voting_recovery_secret_hash(
+    conn: &DbConn,
+    voting: &Voting,
+    recovery_secret_hash: &String,
+) -> QueryResult<usize> {
+    use crate::schema::votings;
+
+    diesel::update(voting)
+        .set(votings::recovery_secret_hash.eq(recovery_secret_hash))
+        .execute(&**conn)
+}
@@ -0,0 +1,135 @@
+//! Reading and writing of CSV (RFC 4180) for the bulk voter import and the
+//! voter key sheet.
+
+use std::mem;
+
+/// Splits the input into records of fields. Fields can be quoted with `"`,
+/// a quote inside a quoted field is doubled. Empty lines are skipped.
+pub fn parse(input: &str) -> Result<Vec<Vec<String>>, String> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+
+    let mut chars = input.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (quoted, c) {
+            (true, '"') if chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            (true, '"') => quoted = false,
+            (true, c) => field.push(c),
+            (false, '"') => quoted = true,
+            (false, ',') => record.push(mem::take(&mut field)),
+            (false, '\r') => {}
+            (false, '\n') => {
+                record.push(mem::take(&mut field));
+                push_record(&mut records, mem::take(&mut record));
+            }
+            (false, c) => field.push(c),
+        }
+    }
+    if quoted {
+        return Err("The CSV ends inside a quoted field".to_string());
+    }
+    record.push(field);
+    push_record(&mut records, record);
+
+    Ok(records)
+}
+
+fn push_record(records: &mut Vec<Vec<String>>, record: Vec<String>) {
+    if record.len() > 1 || !record[0].trim().is_empty() {
+        records.push(record);
+    }
+}
+
+/// One line of CSV, quoting the fields that need it. A field starting like a
+/// spreadsheet formula gets a leading `'`, so it is shown as text instead of
+/// being evaluated.
+pub fn write_record(fields: &[&str]) -> String {
+    let mut line = fields
+        .iter()
+        .map(|field| {
+            let field = if field.starts_with(&['=', '+', '-', '@'][..]) {
+                format!("'{}", field)
+            } else {
+                field.to_string()
+            };
+            if field.contains(&[',', '"', '\n', '\r'][..]) {
+                format!("\"{}\"", field.replace('"', "\"\""))
+            } else {
+                field
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(",");
+    line.push_str("\r\n");
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn records(rows: &[&[&str]]) -> Vec<Vec<String>> {
+        rows.iter()
+            .map(|row| row.iter().map(|field| field.to_string()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn parses_quoted_fields_with_commas_and_doubled_quotes() {
+        assert_eq!(
+            parse("\"Doe, Jane\",\"say \"\"hi\"\"\",3\n").unwrap(),
+            records(&[&["Doe, Jane", "say \"hi\"", "3"]])
+        );
+        assert_eq!(
+            parse("\"two\nlines\",x").unwrap(),
+            records(&[&["two\nlines", "x"]])
+        );
+    }
+
+    #[test]
+    fn parses_crlf_line_endings() {
+        assert_eq!(
+            parse("username,weight\r\nalice,2\r\n").unwrap(),
+            records(&[&["username", "weight"], &["alice", "2"]])
+        );
+    }
+
+    #[test]
+    fn skips_blank_lines() {
+        assert_eq!(
+            parse("\nalice\n   \r\n\nbob").unwrap(),
+            records(&[&["alice"], &["bob"]])
+        );
+        assert_eq!(parse("").unwrap(), records(&[]));
+    }
+
+    #[test]
+    fn rejects_an_unterminated_quote() {
+        assert!(parse("alice,\"2\nbob,3\n").is_err());
+    }
+
+    #[test]
+    fn quotes_fields_that_need_it() {
+        assert_eq!(
+            write_record(&["Doe, Jane", "say \"hi\"", "plain"]),
+            "\"Doe, Jane\",\"say \"\"hi\"\"\",plain\r\n"
+        );
+    }
+
+    #[test]
+    fn neutralizes_fields_starting_like_a_formula() {
+        assert_eq!(
+            write_record(&["=1+1", "+1", "-1", "@SUM(A1)", "a=b"]),
+            "'=1+1,'+1,'-1,'@SUM(A1),a=b\r\n"
+        );
+        assert_eq!(
+            write_record(&["=HYPERLINK(\"x\",\"y\")"]),
+            "\"'=HYPERLINK(\"\"x\"\",\"\"y\"\")\"\r\n"
+        );
+    }
+}
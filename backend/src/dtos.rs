@@ -1,7 +1,29 @@
+pub mod clone_voting_dto;
+pub mod create_admin_key_dto;
+pub mod create_poll_dto;
+pub mod create_template_dto;
 pub mod create_voter_dto;
 pub mod create_voting_dto;
 pub mod get_active_poll_dto;
+pub mod get_admin_keys_dto;
+pub mod get_attendance_dto;
+pub mod get_delegations_dto;
+pub mod get_roster_dto;
+pub mod get_template_dto;
 pub mod get_voter_info_dto;
+pub mod get_voters_dto;
 pub mod get_voting_dto;
+pub mod import_voters_dto;
+pub mod recover_voting_dto;
+pub mod regenerate_voter_key_dto;
+pub mod rotate_admin_key_dto;
 pub mod set_active_poll_dto;
+pub mod set_delegation_dto;
+pub mod set_poll_order_dto;
+pub mod set_poll_schedule_dto;
+pub mod set_proxy_dto;
+pub mod set_recovery_secret_dto;
+pub mod set_tie_decision_dto;
 pub mod set_vote_dto;
+pub mod update_poll_dto;
+pub mod update_voter_dto;
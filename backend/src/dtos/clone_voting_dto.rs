@@ -0,0 +1,28 @@
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct CloneVotingRequest {
+    /// Defaults to the name of the cloned voting.
+    pub name: Option<String>,
+    /// Copies the voters that are not revoked with new voter keys. Defaults to
+    /// false.
+    #[serde(rename = "copyVoters")]
+    pub copy_voters: Option<bool>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct CloneVotingResponse {
+    #[serde(rename = "votingId")]
+    pub voting_id: String,
+    #[serde(rename = "adminKey")]
+    pub admin_key: String,
+    pub voters: Vec<CloneVotingVoterResponse>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct CloneVotingVoterResponse {
+    #[serde(rename = "voterId")]
+    pub voter_id: String,
+    pub username: String,
+    #[serde(rename = "voterKey")]
+    pub voter_key: String,
+}
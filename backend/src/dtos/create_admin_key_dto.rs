@@ -0,0 +1,18 @@
+use crate::types::AdminScope;
+
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct CreateAdminKeyRequest {
+    /// Who the key is for, for example "Secretary".
+    pub label: String,
+    pub scope: AdminScope,
+}
+
+#[derive(Serialize, Debug)]
+pub struct CreateAdminKeyResponse {
+    #[serde(rename = "adminKeyId")]
+    pub admin_key_id: String,
+
+    #[serde(rename = "adminKey")]
+    pub admin_key: String,
+}
@@ -0,0 +1,8 @@
+#[derive(Serialize, Debug)]
+pub struct CreatePollResponse {
+    #[serde(rename = "pollId")]
+    pub poll_id: String,
+    /// The poll is appended at the end of the agenda.
+    #[serde(rename = "pollIndex")]
+    pub poll_index: i32,
+}
@@ -0,0 +1,16 @@
+use crate::dtos::create_voting_dto::CreateVotingRequest;
+
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct CreateTemplateRequest {
+    pub name: String,
+    pub voting: CreateVotingRequest,
+}
+
+#[derive(Serialize, Debug)]
+pub struct CreateTemplateResponse {
+    pub name: String,
+    /// Needed to read, use, change or delete the template.
+    #[serde(rename = "adminKey")]
+    pub admin_key: String,
+}
@@ -2,6 +2,15 @@
 #[serde(deny_unknown_fields)]
 pub struct CreateVoterRequest {
     pub username: String,
+    /// Weight of the voter's votes, for example the shares they hold.
+    /// Defaults to 1.
+    pub weight: Option<i32>,
+    /// Only kept for the key distribution of the admin.
+    pub email: Option<String>,
+    /// Makes this voter the casting voter of a voting with the
+    /// `CASTING_VOTER` tie policy, replacing any previous one.
+    #[serde(rename = "castingVoter")]
+    pub casting_voter: Option<bool>,
 }
 
 #[derive(Serialize, Debug)]
@@ -9,6 +18,9 @@ pub struct CreateVoterResponse {
     #[serde(rename = "voterKey")]
     pub voter_key: String,
 
+    #[serde(rename = "voterId")]
+    pub voter_id: String,
+
     #[serde(rename = "votingId")]
     pub voting_id: String,
 }
@@ -1,15 +1,153 @@
-#[derive(Deserialize, Debug)]
+use crate::types::{MajorityRule, PollType, TiePolicy, VoteCounting};
+
+use chrono::NaiveDateTime;
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
 #[serde(deny_unknown_fields)]
 pub struct CreateVotingRequest {
     pub name: String,
+    /// Can be left out when starting from a template, the polls of the
+    /// template are used then.
+    #[serde(default)]
     pub polls: Vec<CreateVotingPollRequest>,
+    /// Name of the template the voting starts from. Settings that are not
+    /// set are taken from the template. Needs the admin key of the template
+    /// as authorization.
+    pub template: Option<String>,
+    /// Defaults to `DRAW`.
+    #[serde(rename = "tiePolicy")]
+    pub tie_policy: Option<TiePolicy>,
+    /// How many ballots of other voters one voter can hold as proxy.
+    /// Defaults to 1, 0 disables proxies.
+    #[serde(rename = "maxProxiesPerVoter")]
+    pub max_proxies_per_voter: Option<i32>,
+    /// Ballots are stored without their voter. Defaults to false.
+    ///
+    /// Known limit: the tables keep no link, but the ballot is written in the
+    /// same transaction as the record that the voter voted. Someone with
+    /// direct database access can still match both through system columns
+    /// like `xmin`, the write-ahead log or the order of the rows. Secret
+    /// ballots protect against admins and the API, not against the database
+    /// operator.
+    pub secret: Option<bool>,
+    /// Voters can replace their ballot while the poll is active. Defaults to
+    /// false.
+    #[serde(rename = "allowVoteChange")]
+    pub allow_vote_change: Option<bool>,
+    /// How often a voter can change their vote on one poll. Unlimited when
+    /// not set.
+    #[serde(rename = "maxVoteChanges")]
+    pub max_vote_changes: Option<i32>,
+    /// The agenda moves to the next poll once every voter voted or the time
+    /// limit of the poll ran out. Defaults to false.
+    #[serde(rename = "autoAdvance")]
+    pub auto_advance: Option<bool>,
+    /// Also returns a recovery secret that issues a new `FULL` admin key when
+    /// the admin keys are lost or leaked. Defaults to false.
+    pub recoverable: Option<bool>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug)]
 #[serde(deny_unknown_fields)]
 pub struct CreateVotingPollRequest {
     pub name: String,
     pub description: String,
+    /// Defaults to `SINGLE_CHOICE` when options are given and to `YES_NO`
+    /// otherwise.
+    #[serde(rename = "pollType")]
+    pub poll_type: Option<PollType>,
+    /// Names of the options a voter can choose from. Polls without options
+    /// are created as `YES_NO` polls with the accept/decline/abstain preset.
+    pub options: Option<Vec<String>>,
+    /// Only for `APPROVAL` polls, defaults to 1.
+    #[serde(rename = "minApprovals")]
+    pub min_approvals: Option<i32>,
+    /// Only for `APPROVAL` polls, defaults to the amount of options.
+    #[serde(rename = "maxApprovals")]
+    pub max_approvals: Option<i32>,
+    /// Only for `SCORE` polls, defaults to 5. Scores start at 0.
+    #[serde(rename = "maxScore")]
+    pub max_score: Option<i32>,
+    /// Defaults to `SIMPLE`.
+    #[serde(rename = "majorityRule")]
+    pub majority_rule: Option<MajorityRule>,
+    /// Minimum turnout in percent of all voters.
+    #[serde(rename = "quorumPercent")]
+    pub quorum_percent: Option<i32>,
+    /// Defaults to `HEADS`.
+    #[serde(rename = "voteCounting")]
+    pub vote_counting: Option<VoteCounting>,
+    /// Voters who do not vote on the poll are represented by their delegate
+    /// for this topic.
+    pub topic: Option<String>,
+    /// The poll opens by itself at this time (UTC) unless it was opened
+    /// before.
+    #[serde(rename = "opensAt")]
+    pub opens_at: Option<NaiveDateTime>,
+    /// The poll closes by itself at this time (UTC).
+    #[serde(rename = "closesAt")]
+    pub closes_at: Option<NaiveDateTime>,
+    /// Seconds the poll stays open before an auto-advancing agenda moves on.
+    #[serde(rename = "timeLimitSeconds")]
+    pub time_limit_seconds: Option<i32>,
+    /// Counts quorum and majority against the voters who were present when
+    /// the poll opened instead of all voters. Only they can vote on the poll.
+    /// A proxy holder who was present also casts the ballots they hold, and
+    /// those voters count as well.
+    #[serde(rename = "presentVotersOnly")]
+    pub present_voters_only: Option<bool>,
+}
+
+impl CreateVotingRequest {
+    pub fn with_template(self, template: CreateVotingRequest) -> CreateVotingRequest {
+        CreateVotingRequest {
+            name: self.name,
+            polls: match self.polls.is_empty() {
+                true => template.polls,
+                false => self.polls,
+            },
+            template: None,
+            tie_policy: self.tie_policy.or(template.tie_policy),
+            max_proxies_per_voter: self
+                .max_proxies_per_voter
+                .or(template.max_proxies_per_voter),
+            secret: self.secret.or(template.secret),
+            allow_vote_change: self.allow_vote_change.or(template.allow_vote_change),
+            max_vote_changes: self.max_vote_changes.or(template.max_vote_changes),
+            auto_advance: self.auto_advance.or(template.auto_advance),
+            recoverable: self.recoverable.or(template.recoverable),
+        }
+    }
+}
+
+impl CreateVotingPollRequest {
+    pub fn get_poll_type(&self) -> PollType {
+        match (self.poll_type, &self.options) {
+            (Some(poll_type), _) => poll_type,
+            (None, Some(_)) => PollType::SingleChoice,
+            (None, None) => PollType::YesNo,
+        }
+    }
+
+    pub fn get_approval_bounds(&self) -> Option<(i32, i32)> {
+        match self.get_poll_type() {
+            PollType::Approval => {
+                let options_count = self.options.as_ref().map_or(0, |options| options.len());
+                Some((
+                    self.min_approvals.unwrap_or(1),
+                    self.max_approvals.unwrap_or(options_count as i32),
+                ))
+            }
+            _ => None,
+        }
+    }
+
+    pub fn get_max_score(&self) -> Option<i32> {
+        match self.get_poll_type() {
+            PollType::Score => Some(self.max_score.unwrap_or(5)),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Serialize, Debug)]
@@ -18,4 +156,7 @@ pub struct CreateVotingResponse {
     pub voting_id: String,
     #[serde(rename = "adminKey")]
     pub admin_key: String,
+    /// Only set for recoverable votings. It is shown this one time.
+    #[serde(rename = "recoverySecret")]
+    pub recovery_secret: Option<String>,
 }
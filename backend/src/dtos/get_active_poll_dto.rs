@@ -1,8 +1,77 @@
+use crate::types::{PollState, PollType};
+
+use chrono::NaiveDateTime;
+
 #[derive(Serialize, Debug)]
 pub struct GetActivePollResponse {
+    #[serde(rename = "pollId")]
+    pub poll_id: String,
     #[serde(rename = "pollIndex")]
     pub poll_index: i32,
     pub name: String,
     pub description: String,
+    #[serde(rename = "pollType")]
+    pub poll_type: PollType,
+    /// Voters can only vote while the poll is `OPEN`.
+    pub state: PollState,
+    /// The poll closes by itself at this time (UTC).
+    #[serde(rename = "closesAt")]
+    pub closes_at: Option<NaiveDateTime>,
+    pub topic: Option<String>,
+    /// The voter's delegate for the topic of the poll. The delegate's vote
+    /// counts for the voter unless they vote themselves.
+    #[serde(rename = "delegateUsername")]
+    pub delegate_username: Option<String>,
+    pub options: Vec<GetActivePollOptionResponse>,
+    #[serde(rename = "minApprovals")]
+    pub min_approvals: Option<i32>,
+    #[serde(rename = "maxApprovals")]
+    pub max_approvals: Option<i32>,
+    #[serde(rename = "maxScore")]
+    pub max_score: Option<i32>,
+    /// `ACCEPT`, `DECLINE` or `ABSTAIN` for `YES_NO` polls and `VOTED` for
+    /// every other poll type. `None` as long as the voter has not voted.
+    /// Always `VOTED` in secret votings and without the chosen options.
     pub voted: Option<String>,
+    #[serde(rename = "votedOptionIds")]
+    pub voted_option_ids: Option<Vec<String>>,
+    #[serde(rename = "votedScores")]
+    pub voted_scores: Option<Vec<GetActivePollScoreResponse>>,
+    /// When the current ballot was cast. `None` in secret votings.
+    #[serde(rename = "votedAt")]
+    pub voted_at: Option<NaiveDateTime>,
+    /// How often the voter replaced their ballot on this poll.
+    #[serde(rename = "voteChanges")]
+    pub vote_changes: i32,
+    #[serde(rename = "canChangeVote")]
+    pub can_change_vote: bool,
+    /// The voter holding this voter's ballot. This voter can not vote
+    /// themselves while it is set.
+    #[serde(rename = "proxyUsername")]
+    pub proxy_username: Option<String>,
+    /// Ballots of other voters this voter casts as proxy.
+    #[serde(rename = "proxyBallots")]
+    pub proxy_ballots: Vec<GetActivePollBallotResponse>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct GetActivePollBallotResponse {
+    #[serde(rename = "voterId")]
+    pub voter_id: String,
+    pub username: String,
+    pub voted: Option<String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct GetActivePollOptionResponse {
+    #[serde(rename = "optionId")]
+    pub option_id: String,
+    pub name: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct GetActivePollScoreResponse {
+    #[serde(rename = "optionId")]
+    pub option_id: String,
+    pub score: i32,
 }
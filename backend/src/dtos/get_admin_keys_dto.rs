@@ -0,0 +1,13 @@
+use crate::types::AdminScope;
+
+use chrono::NaiveDateTime;
+
+#[derive(Serialize, Debug)]
+pub struct GetAdminKeyResponse {
+    #[serde(rename = "adminKeyId")]
+    pub admin_key_id: String,
+    pub label: String,
+    pub scope: AdminScope,
+    #[serde(rename = "createdAt")]
+    pub created_at: NaiveDateTime,
+}
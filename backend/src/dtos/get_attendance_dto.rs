@@ -0,0 +1,21 @@
+use chrono::NaiveDateTime;
+
+#[derive(Serialize, Debug)]
+pub struct GetAttendanceResponse {
+    #[serde(rename = "presentVoterCount")]
+    pub present_voter_count: i32,
+    /// Every time a voter was present, ordered by check-in.
+    pub timeline: Vec<GetAttendanceEntryResponse>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct GetAttendanceEntryResponse {
+    #[serde(rename = "voterId")]
+    pub voter_id: String,
+    pub username: String,
+    #[serde(rename = "checkedInAt")]
+    pub checked_in_at: NaiveDateTime,
+    /// `None` while the voter is still present.
+    #[serde(rename = "checkedOutAt")]
+    pub checked_out_at: Option<NaiveDateTime>,
+}
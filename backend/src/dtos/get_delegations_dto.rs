@@ -0,0 +1,8 @@
+#[derive(Serialize, Debug)]
+pub struct GetDelegationResponse {
+    pub topic: String,
+    #[serde(rename = "delegateVoterId")]
+    pub delegate_voter_id: String,
+    #[serde(rename = "delegateUsername")]
+    pub delegate_username: String,
+}
@@ -0,0 +1,33 @@
+use chrono::NaiveDateTime;
+
+#[derive(Serialize, Debug)]
+pub struct GetRosterVoterResponse {
+    #[serde(rename = "voterId")]
+    pub voter_id: String,
+    pub username: String,
+    pub weight: i32,
+    #[serde(rename = "revokedAt")]
+    pub revoked_at: Option<NaiveDateTime>,
+    /// One entry for every poll of the voting, in agenda order.
+    pub polls: Vec<GetRosterPollResponse>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct GetRosterPollResponse {
+    #[serde(rename = "pollId")]
+    pub poll_id: String,
+    pub voted: bool,
+    /// The proxy who cast the ballot for the voter.
+    #[serde(rename = "proxyVoterId")]
+    pub proxy_voter_id: Option<String>,
+    /// What the voter chose, ordered by rank. Always `None` in secret votings.
+    pub choices: Option<Vec<GetRosterChoiceResponse>>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct GetRosterChoiceResponse {
+    #[serde(rename = "optionId")]
+    pub option_id: String,
+    pub rank: i32,
+    pub score: Option<i32>,
+}
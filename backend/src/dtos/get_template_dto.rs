@@ -0,0 +1,7 @@
+use crate::dtos::create_voting_dto::CreateVotingRequest;
+
+#[derive(Serialize, Debug)]
+pub struct GetTemplateResponse {
+    pub name: String,
+    pub voting: CreateVotingRequest,
+}
@@ -2,5 +2,7 @@
 pub struct GetVoterInfoResponse {
     #[serde(rename = "votingName")]
     pub voting_name: String,
+    #[serde(rename = "voterId")]
+    pub voter_id: String,
     pub username: String,
 }
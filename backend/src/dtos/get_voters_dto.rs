@@ -0,0 +1,13 @@
+use chrono::NaiveDateTime;
+
+#[derive(Serialize, Debug)]
+pub struct GetVoterResponse {
+    #[serde(rename = "voterId")]
+    pub voter_id: String,
+    pub username: String,
+    pub weight: i32,
+    pub email: Option<String>,
+    /// Revoked voters can not vote anymore, their past ballots are kept.
+    #[serde(rename = "revokedAt")]
+    pub revoked_at: Option<NaiveDateTime>,
+}
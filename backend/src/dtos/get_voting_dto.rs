@@ -1,3 +1,7 @@
+use crate::types::{MajorityRule, PollState, PollType, TiePolicy, VoteCounting};
+
+use chrono::NaiveDateTime;
+
 #[derive(Serialize, Debug)]
 pub struct GetVotingResponse {
     #[serde(rename = "votingId")]
@@ -5,9 +9,48 @@ pub struct GetVotingResponse {
     pub name: String,
     #[serde(rename = "voterCount")]
     pub voter_count: i32,
+    /// Sum of the weights of all voters.
+    #[serde(rename = "voterWeight")]
+    pub voter_weight: i64,
     pub polls: Vec<GetVotingPollsResponse>,
+    /// The first open poll, for clients showing a single active poll.
     #[serde(rename = "activePollIndex")]
     pub active_poll_index: Option<i32>,
+    #[serde(rename = "openPollIndexes")]
+    pub open_poll_indexes: Vec<i32>,
+    #[serde(rename = "tiePolicy")]
+    pub tie_policy: TiePolicy,
+    #[serde(rename = "castingVoterUsername")]
+    pub casting_voter_username: Option<String>,
+    #[serde(rename = "maxProxiesPerVoter")]
+    pub max_proxies_per_voter: i32,
+    pub proxies: Vec<GetVotingProxyResponse>,
+    pub secret: bool,
+    #[serde(rename = "allowVoteChange")]
+    pub allow_vote_change: bool,
+    #[serde(rename = "maxVoteChanges")]
+    pub max_vote_changes: Option<i32>,
+    /// The current poll of the agenda, moved by the agenda endpoints.
+    #[serde(rename = "agendaPollIndex")]
+    pub agenda_poll_index: Option<i32>,
+    #[serde(rename = "autoAdvance")]
+    pub auto_advance: bool,
+    /// Whether the voting has a recovery secret.
+    pub recoverable: bool,
+    /// Archived votings are read-only.
+    #[serde(rename = "archivedAt")]
+    pub archived_at: Option<NaiveDateTime>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct GetVotingProxyResponse {
+    #[serde(rename = "voterId")]
+    pub voter_id: String,
+    pub username: String,
+    #[serde(rename = "proxyVoterId")]
+    pub proxy_voter_id: String,
+    #[serde(rename = "proxyUsername")]
+    pub proxy_username: String,
 }
 
 #[derive(Serialize, Debug)]
@@ -17,6 +60,56 @@ pub struct GetVotingPollsResponse {
     pub name: String,
     pub status: String,
     pub description: String,
+    #[serde(rename = "pollType")]
+    pub poll_type: PollType,
+    #[serde(rename = "majorityRule")]
+    pub majority_rule: MajorityRule,
+    #[serde(rename = "quorumPercent")]
+    pub quorum_percent: Option<i32>,
+    #[serde(rename = "voteCounting")]
+    pub vote_counting: VoteCounting,
+    pub topic: Option<String>,
+    pub state: PollState,
+    #[serde(rename = "openedAt")]
+    pub opened_at: Option<NaiveDateTime>,
+    #[serde(rename = "closedAt")]
+    pub closed_at: Option<NaiveDateTime>,
+    /// Whether the result can not change anymore, which is the case once
+    /// the poll is closed.
+    #[serde(rename = "final")]
+    pub is_final: bool,
+    #[serde(rename = "opensAt")]
+    pub opens_at: Option<NaiveDateTime>,
+    #[serde(rename = "closesAt")]
+    pub closes_at: Option<NaiveDateTime>,
+    /// The status and winner recorded when the poll was closed.
+    #[serde(rename = "finalStatus")]
+    pub final_status: Option<String>,
+    #[serde(rename = "finalWinnerOptionId")]
+    pub final_winner_option_id: Option<String>,
+    #[serde(rename = "timeLimitSeconds")]
+    pub time_limit_seconds: Option<i32>,
+    #[serde(rename = "presentVotersOnly")]
+    pub present_voters_only: bool,
+    /// The voters quorum and majority of the poll are counted against.
+    #[serde(rename = "voterCount")]
+    pub voter_count: i32,
+    #[serde(rename = "voterWeight")]
+    pub voter_weight: i64,
+    #[serde(rename = "winnerOptionId")]
+    pub winner_option_id: Option<String>,
+    /// Options tied for the lead, empty when there is no tie.
+    #[serde(rename = "tiedOptionIds")]
+    pub tied_option_ids: Vec<String>,
+    /// How the tie was resolved: `REJECTED`, `CHAIR_DECISION` or
+    /// `CASTING_VOTE`. Missing when the poll is not tied or the tie is
+    /// unresolved.
+    #[serde(rename = "tieResolution")]
+    pub tie_resolution: Option<String>,
+    pub options: Vec<GetVotingPollOptionsResponse>,
+    #[serde(rename = "instantRunoff")]
+    pub instant_runoff: Option<GetVotingInstantRunoffResponse>,
+    pub schulze: Option<GetVotingSchulzeResponse>,
     #[serde(rename = "votesAccept")]
     pub votes_accept: i64,
     #[serde(rename = "votesDecline")]
@@ -25,4 +118,85 @@ pub struct GetVotingPollsResponse {
     pub votes_abstain: i64,
     #[serde(rename = "votesTotal")]
     pub votes_total: i64,
+    #[serde(rename = "weightedVotesAccept")]
+    pub weighted_votes_accept: i64,
+    #[serde(rename = "weightedVotesDecline")]
+    pub weighted_votes_decline: i64,
+    #[serde(rename = "weightedVotesAbstain")]
+    pub weighted_votes_abstain: i64,
+    #[serde(rename = "weightedVotesTotal")]
+    pub weighted_votes_total: i64,
+    /// Votes cast by a proxy on behalf of another voter.
+    #[serde(rename = "proxyVotes")]
+    pub proxy_votes: i64,
+    /// Votes of voters who voted themselves. All totals above include the
+    /// delegated votes.
+    #[serde(rename = "directVotes")]
+    pub direct_votes: i64,
+    #[serde(rename = "delegatedVotes")]
+    pub delegated_votes: i64,
+    /// Delegation cycles on the topic of the poll as lists of voter ids.
+    /// Voters in or delegating into a cycle are not counted.
+    #[serde(rename = "delegationCycles")]
+    pub delegation_cycles: Vec<Vec<String>>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct GetVotingPollOptionsResponse {
+    #[serde(rename = "optionId")]
+    pub option_id: String,
+    pub name: String,
+    pub votes: i64,
+    #[serde(rename = "weightedVotes")]
+    pub weighted_votes: i64,
+    #[serde(rename = "scoreTotal")]
+    pub score_total: Option<i64>,
+    #[serde(rename = "weightedScoreTotal")]
+    pub weighted_score_total: Option<i64>,
+    /// Average score per ballot, weighted when the poll counts by weights.
+    #[serde(rename = "scoreAverage")]
+    pub score_average: Option<f64>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct GetVotingInstantRunoffResponse {
+    pub rounds: Vec<GetVotingInstantRunoffRoundResponse>,
+    #[serde(rename = "winnerOptionId")]
+    pub winner_option_id: Option<String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct GetVotingInstantRunoffRoundResponse {
+    pub votes: Vec<GetVotingInstantRunoffVotesResponse>,
+    pub exhausted: i64,
+    #[serde(rename = "eliminatedOptionId")]
+    pub eliminated_option_id: Option<String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct GetVotingInstantRunoffVotesResponse {
+    #[serde(rename = "optionId")]
+    pub option_id: String,
+    pub votes: i64,
+}
+
+#[derive(Serialize, Debug)]
+pub struct GetVotingSchulzeResponse {
+    /// Row and column order of both matrices.
+    #[serde(rename = "optionIds")]
+    pub option_ids: Vec<String>,
+    #[serde(rename = "pairwisePreferences")]
+    pub pairwise_preferences: Vec<Vec<i64>>,
+    #[serde(rename = "strongestPaths")]
+    pub strongest_paths: Vec<Vec<i64>>,
+    pub ranking: Vec<GetVotingSchulzeRankingResponse>,
+    #[serde(rename = "winnerOptionId")]
+    pub winner_option_id: Option<String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct GetVotingSchulzeRankingResponse {
+    #[serde(rename = "optionId")]
+    pub option_id: String,
+    pub rank: i64,
 }
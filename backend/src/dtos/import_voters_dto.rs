@@ -0,0 +1,16 @@
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct ImportVotersRequest {
+    pub voters: Vec<ImportVoterRequest>,
+}
+
+/// A row of the import. In a CSV import the header row names the columns
+/// `username`, `weight` and `email`, only `username` is required.
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct ImportVoterRequest {
+    pub username: String,
+    /// Defaults to 1.
+    pub weight: Option<i32>,
+    pub email: Option<String>,
+}
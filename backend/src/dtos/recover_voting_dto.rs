@@ -0,0 +1,12 @@
+#[derive(Serialize, Debug)]
+pub struct RecoverVotingResponse {
+    #[serde(rename = "adminKeyId")]
+    pub admin_key_id: String,
+
+    #[serde(rename = "adminKey")]
+    pub admin_key: String,
+
+    /// Replaces the recovery secret that was used.
+    #[serde(rename = "recoverySecret")]
+    pub recovery_secret: String,
+}
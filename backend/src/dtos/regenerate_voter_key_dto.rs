@@ -0,0 +1,8 @@
+#[derive(Serialize, Debug)]
+pub struct RegenerateVoterKeyResponse {
+    #[serde(rename = "voterKey")]
+    pub voter_key: String,
+
+    #[serde(rename = "voterId")]
+    pub voter_id: String,
+}
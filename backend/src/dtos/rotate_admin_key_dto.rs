@@ -0,0 +1,8 @@
+#[derive(Serialize, Debug)]
+pub struct RotateAdminKeyResponse {
+    #[serde(rename = "adminKeyId")]
+    pub admin_key_id: String,
+
+    #[serde(rename = "adminKey")]
+    pub admin_key: String,
+}
@@ -3,4 +3,6 @@
 pub struct SetActivePollRequest {
     #[serde(rename = "pollIndex")]
     pub poll_index: Option<u16>,
+    #[serde(rename = "pollId")]
+    pub poll_id: Option<String>,
 }
@@ -0,0 +1,7 @@
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SetDelegationRequest {
+    pub topic: String,
+    #[serde(rename = "delegateVoterId")]
+    pub delegate_voter_id: String,
+}
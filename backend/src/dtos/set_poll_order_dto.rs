@@ -0,0 +1,7 @@
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SetPollOrderRequest {
+    /// Ids of every poll of the voting in their new order.
+    #[serde(rename = "pollIds")]
+    pub poll_ids: Vec<String>,
+}
@@ -0,0 +1,10 @@
+use chrono::NaiveDateTime;
+
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SetPollScheduleRequest {
+    #[serde(rename = "opensAt")]
+    pub opens_at: Option<NaiveDateTime>,
+    #[serde(rename = "closesAt")]
+    pub closes_at: Option<NaiveDateTime>,
+}
@@ -0,0 +1,7 @@
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SetProxyRequest {
+    /// The voter who casts the ballot from now on.
+    #[serde(rename = "proxyVoterId")]
+    pub proxy_voter_id: String,
+}
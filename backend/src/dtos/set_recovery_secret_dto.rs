@@ -0,0 +1,14 @@
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SetRecoverySecretRequest {
+    /// Required when the voting already has a recovery secret, so a leaked
+    /// admin key can not replace it.
+    #[serde(rename = "currentRecoverySecret")]
+    pub current_recovery_secret: Option<String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct SetRecoverySecretResponse {
+    #[serde(rename = "recoverySecret")]
+    pub recovery_secret: String,
+}
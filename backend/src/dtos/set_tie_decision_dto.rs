@@ -0,0 +1,6 @@
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SetTieDecisionRequest {
+    #[serde(rename = "optionId")]
+    pub option_id: String,
+}
@@ -1,5 +1,27 @@
 #[derive(Deserialize, Debug)]
 #[serde(deny_unknown_fields)]
 pub struct SetVoteRequest {
+    #[serde(rename = "optionId")]
+    pub option_id: Option<String>,
+    /// Ranked option ids from the most to the least preferred one for ranked
+    /// polls and the approved option ids for `APPROVAL` polls.
+    #[serde(rename = "optionIds")]
+    pub option_ids: Option<Vec<String>>,
+    /// One score for every option of a `SCORE` poll.
+    pub scores: Option<Vec<SetVoteScoreRequest>>,
+    /// Legacy answer for `YES_NO` polls, used when no `optionId` is given.
+    /// `true` accepts, `false` declines and `null` abstains.
     pub answer: Option<bool>,
+    /// The voter whose ballot is cast. Defaults to the authenticated voter,
+    /// another voter's ballot can be cast when it was given to them as proxy.
+    #[serde(rename = "voterId")]
+    pub voter_id: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SetVoteScoreRequest {
+    #[serde(rename = "optionId")]
+    pub option_id: String,
+    pub score: i32,
 }
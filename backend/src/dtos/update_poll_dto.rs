@@ -0,0 +1,7 @@
+/// Fields that are not set keep their value.
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct UpdatePollRequest {
+    pub name: Option<String>,
+    pub description: Option<String>,
+}
@@ -0,0 +1,5 @@
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct UpdateVoterRequest {
+    pub username: String,
+}
@@ -19,12 +19,16 @@ extern crate r2d2;
 extern crate r2d2_diesel;
 
 mod actions;
+mod csv;
 mod dtos;
 mod models;
 mod pool;
 mod routes;
+mod scheduler;
 pub mod schema;
 pub mod schema_custom;
+mod tally;
+mod types;
 mod utils;
 mod validators;
 
@@ -34,10 +38,11 @@ use env_logger::Builder;
 use log::LevelFilter;
 use std::env;
 use std::io::Write;
+use std::time::Duration;
 
-use routes::{poll, vote, voter, voting};
-use rocket::Config;
 use rocket::config::Environment;
+use rocket::Config;
+use routes::{admin_key, agenda, attendance, delegation, poll, template, vote, voter, voting};
 
 fn main() {
     dotenv().ok();
@@ -64,6 +69,22 @@ fn main() {
     )
     .expect("Could run migrations");
 
+    scheduler::start(
+        postgre_connection_poll.clone(),
+        Duration::from_secs(
+            env::var("SCHEDULER_INTERVAL_SECONDS")
+                .unwrap_or("10".to_string())
+                .parse::<u64>()
+                .expect("SCHEDULER_INTERVAL_SECONDS must be a number of seconds"),
+        ),
+        chrono::Duration::days(
+            env::var("ARCHIVE_RETENTION_DAYS")
+                .unwrap_or("365".to_string())
+                .parse::<i64>()
+                .expect("ARCHIVE_RETENTION_DAYS must be a number of days"),
+        ),
+    );
+
     let config = Config::build(Environment::Staging)
         .address(env::var("ADDRESS").unwrap_or("0.0.0.0".to_string()))
         .port(
@@ -80,13 +101,62 @@ fn main() {
         .mount(
             "/api",
             routes![
+                admin_key::get_admin_keys,
+                admin_key::create_admin_key,
+                admin_key::delete_admin_key,
+                admin_key::rotate_admin_key,
+                admin_key::set_recovery_secret,
+                admin_key::recover_voting,
+                agenda::next_agenda_poll,
+                agenda::previous_agenda_poll,
+                attendance::get_attendance,
+                attendance::check_in,
+                attendance::check_out,
+                attendance::check_in_by_admin,
+                attendance::check_out_by_admin,
+                delegation::get_delegations,
+                delegation::set_delegation,
+                delegation::delete_delegation,
+                poll::close_poll,
+                poll::close_poll_by_id,
+                poll::create_poll,
+                poll::delete_poll_at_index,
+                poll::delete_poll_by_id,
                 poll::get_active_poll,
+                poll::open_poll,
+                poll::open_poll_by_id,
+                poll::reopen_poll,
+                poll::reopen_poll_by_id,
                 poll::set_active_poll,
+                poll::set_poll_order,
+                poll::set_poll_schedule,
+                poll::set_poll_schedule_by_id,
+                poll::update_poll,
+                poll::update_poll_by_id,
+                template::create_template,
+                template::delete_template,
+                template::get_template,
+                template::update_template,
+                voting::archive_voting,
+                voting::clone_voting,
                 voting::create_voting,
+                voting::delete_voting,
                 voting::get_voting,
+                voting::set_tie_decision,
+                voting::set_tie_decision_by_poll_id,
                 vote::set_vote,
+                vote::set_vote_by_poll_id,
                 voter::create_voter,
+                voter::import_voters,
+                voter::import_voters_csv,
                 voter::get_voter_info,
+                voter::set_proxy,
+                voter::delete_proxy,
+                voter::get_voters,
+                voter::update_voter,
+                voter::revoke_voter,
+                voter::regenerate_voter_key,
+                voter::get_roster,
             ],
         )
         .register(catchers![routes::unauthorized])
@@ -1,15 +1,62 @@
+use crate::schema::admin_keys;
+use crate::schema::attendances;
+use crate::schema::delegations;
+use crate::schema::named_votes;
+use crate::schema::participations;
+use crate::schema::poll_options;
 use crate::schema::polls;
+use crate::schema::proxies;
+use crate::schema::vote_choices;
 use crate::schema::voters;
 use crate::schema::votes;
+use crate::schema::voting_templates;
 use crate::schema::votings;
+use crate::schema_custom::poll_option_results;
 use crate::schema_custom::poll_results;
+use crate::types::{
+    AdminScope, MajorityRule, PollState, PollType, PresetAnswer, TiePolicy, VoteCounting,
+};
+
+use chrono::NaiveDateTime;
 
 #[derive(Queryable, Insertable, PartialEq, Identifiable, Debug)]
 pub struct Voting {
     pub id: String,
-    pub admin_key_hash: String,
     pub name: String,
-    pub active_poll_index: Option<i32>,
+    pub tie_policy: TiePolicy,
+    pub casting_voter_fk: Option<String>,
+    pub max_proxies_per_voter: i32,
+    pub secret: bool,
+    pub allow_vote_change: bool,
+    pub max_vote_changes: Option<i32>,
+    pub agenda_poll_index: Option<i32>,
+    pub auto_advance: bool,
+    pub archived_at: Option<NaiveDateTime>,
+    pub recovery_secret_hash: Option<String>,
+}
+
+/// A credential to administrate a voting, limited to its scope.
+#[derive(Queryable, Insertable, PartialEq, Identifiable, Debug)]
+pub struct AdminKey {
+    pub id: String,
+    pub voting_fk: String,
+    pub key_hash: String,
+    pub label: String,
+    pub scope: AdminScope,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Insertable, Debug)]
+#[table_name = "votings"]
+pub struct NewVoting<'a> {
+    pub name: &'a String,
+    pub tie_policy: TiePolicy,
+    pub max_proxies_per_voter: i32,
+    pub secret: bool,
+    pub allow_vote_change: bool,
+    pub max_vote_changes: Option<i32>,
+    pub auto_advance: bool,
+    pub recovery_secret_hash: Option<&'a String>,
 }
 
 #[derive(Queryable, Insertable, PartialEq, Identifiable, Debug)]
@@ -19,6 +66,91 @@ pub struct Poll {
     pub voting_fk: String,
     pub name: String,
     pub description: String,
+    pub poll_type: PollType,
+    pub min_approvals: Option<i32>,
+    pub max_approvals: Option<i32>,
+    pub max_score: Option<i32>,
+    pub majority_rule: MajorityRule,
+    pub quorum_percent: Option<i32>,
+    pub chair_decision_option_fk: Option<String>,
+    pub vote_counting: VoteCounting,
+    pub topic: Option<String>,
+    pub state: PollState,
+    pub opened_at: Option<NaiveDateTime>,
+    pub closed_at: Option<NaiveDateTime>,
+    pub opens_at: Option<NaiveDateTime>,
+    pub closes_at: Option<NaiveDateTime>,
+    pub final_status: Option<String>,
+    pub final_winner_option_fk: Option<String>,
+    pub time_limit_seconds: Option<i32>,
+    pub present_voters_only: bool,
+}
+
+#[derive(Insertable, Debug)]
+#[table_name = "polls"]
+pub struct NewPoll<'a> {
+    pub sequenz_number: i32,
+    pub voting_fk: &'a String,
+    pub name: &'a String,
+    pub description: &'a String,
+    pub poll_type: PollType,
+    pub min_approvals: Option<i32>,
+    pub max_approvals: Option<i32>,
+    pub max_score: Option<i32>,
+    pub majority_rule: MajorityRule,
+    pub quorum_percent: Option<i32>,
+    pub vote_counting: VoteCounting,
+    pub topic: Option<&'a String>,
+    pub opens_at: Option<NaiveDateTime>,
+    pub closes_at: Option<NaiveDateTime>,
+    pub time_limit_seconds: Option<i32>,
+    pub present_voters_only: bool,
+}
+
+#[derive(Queryable, Insertable, PartialEq, Identifiable, Debug)]
+pub struct PollOption {
+    pub id: String,
+    pub sequenz_number: i32,
+    pub poll_fk: String,
+    pub name: String,
+    pub preset_answer: Option<PresetAnswer>,
+}
+
+#[derive(Queryable, Insertable, PartialEq, Identifiable, Debug)]
+#[table_name = "proxies"]
+pub struct Proxy {
+    pub id: String,
+    pub voting_fk: String,
+    pub voter_fk: String,
+    pub proxy_voter_fk: String,
+}
+
+/// A named definition new votings can start from.
+#[derive(Queryable, Insertable, PartialEq, Identifiable, Debug)]
+pub struct VotingTemplate {
+    pub id: String,
+    pub name: String,
+    pub admin_key_hash: String,
+    pub definition: String,
+}
+
+/// A voter is present from `checked_in_at` until `checked_out_at`.
+#[derive(Queryable, Insertable, PartialEq, Identifiable, Debug)]
+pub struct Attendance {
+    pub id: String,
+    pub voting_fk: String,
+    pub voter_fk: String,
+    pub checked_in_at: NaiveDateTime,
+    pub checked_out_at: Option<NaiveDateTime>,
+}
+
+#[derive(Queryable, Insertable, PartialEq, Identifiable, Debug)]
+pub struct Delegation {
+    pub id: String,
+    pub voting_fk: String,
+    pub voter_fk: String,
+    pub delegate_voter_fk: String,
+    pub topic: String,
 }
 
 #[derive(Queryable, Insertable, PartialEq, Identifiable, Debug)]
@@ -27,14 +159,49 @@ pub struct Voter {
     pub voter_key_hash: String,
     pub voting_fk: String,
     pub username: String,
+    pub weight: i32,
+    pub email: Option<String>,
+    pub revoked_at: Option<NaiveDateTime>,
 }
 
+/// The fact that a voter voted on a poll. In secret votings it is the only
+/// record of the voter, the ballot itself is stored without them.
+#[derive(Identifiable, Queryable, PartialEq, Insertable, Debug)]
+pub struct Participation {
+    pub id: String,
+    pub poll_fk: String,
+    pub voter_fk: String,
+    pub proxy_voter_fk: Option<String>,
+}
+
+/// The content of a ballot lives in its choices. Nothing on it leads back to
+/// the voter.
 #[derive(Identifiable, Queryable, PartialEq, Insertable)]
 pub struct Vote {
     pub id: String,
     pub poll_fk: String,
+}
+
+/// Who cast a ballot of a voting that is not secret, with which weight and
+/// when. Secret ballots have none.
+#[derive(Identifiable, Queryable, PartialEq, Insertable, Debug)]
+#[primary_key(vote_fk)]
+pub struct NamedVote {
+    pub vote_fk: String,
+    pub poll_fk: String,
     pub voter_fk: String,
-    pub answer: Option<bool>,
+    pub weight: i32,
+    pub voted_at: NaiveDateTime,
+    pub replaced_at: Option<NaiveDateTime>,
+}
+
+#[derive(Identifiable, Queryable, PartialEq, Insertable, Debug)]
+pub struct VoteChoice {
+    pub id: String,
+    pub vote_fk: String,
+    pub poll_option_fk: String,
+    pub rank: i32,
+    pub score: Option<i32>,
 }
 
 #[derive(Queryable, PartialEq, Identifiable, Debug)]
@@ -44,8 +211,42 @@ pub struct PollResult {
     pub voting_fk: String,
     pub name: String,
     pub description: String,
+    pub poll_type: PollType,
+    pub majority_rule: MajorityRule,
+    pub quorum_percent: Option<i32>,
+    pub chair_decision_option_fk: Option<String>,
+    pub vote_counting: VoteCounting,
+    pub topic: Option<String>,
+    pub state: PollState,
+    pub opened_at: Option<NaiveDateTime>,
+    pub closed_at: Option<NaiveDateTime>,
+    pub opens_at: Option<NaiveDateTime>,
+    pub closes_at: Option<NaiveDateTime>,
+    pub final_status: Option<String>,
+    pub final_winner_option_fk: Option<String>,
+    pub time_limit_seconds: Option<i32>,
+    pub present_voters_only: bool,
     pub votes_accept: i64,
     pub votes_decline: i64,
     pub votes_abstain: i64,
     pub votes_total: i64,
+    pub weighted_votes_accept: i64,
+    pub weighted_votes_decline: i64,
+    pub weighted_votes_abstain: i64,
+    pub weighted_votes_total: i64,
+    pub proxy_votes: i64,
+}
+
+#[derive(Queryable, PartialEq, Identifiable, Debug)]
+pub struct PollOptionResult {
+    pub id: String,
+    pub sequenz_number: i32,
+    pub poll_fk: String,
+    pub voting_fk: String,
+    pub name: String,
+    pub preset_answer: Option<PresetAnswer>,
+    pub votes: i64,
+    pub score_total: i64,
+    pub weighted_votes: i64,
+    pub weighted_score_total: i64,
 }
@@ -9,7 +9,7 @@ use std::ops::Deref;
 type DbType = diesel::pg::PgConnection;
 
 use r2d2_diesel::ConnectionManager;
-type Pool = r2d2::Pool<ConnectionManager<DbType>>;
+pub type Pool = r2d2::Pool<ConnectionManager<DbType>>;
 type PoolConn = r2d2::PooledConnection<ConnectionManager<DbType>>;
 
 pub struct DbConn(pub PoolConn);
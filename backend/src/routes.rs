@@ -11,7 +11,12 @@ pub fn unauthorized(_req: &Request) -> ErrorResponse {
     }
 }
 
+pub mod admin_key;
+pub mod agenda;
+pub mod attendance;
+pub mod delegation;
 pub mod poll;
+pub mod template;
 pub mod vote;
 pub mod voter;
 pub mod voting;
@@ -0,0 +1,248 @@
+use crate::pool::DbConn;
+
+use crate::actions::check::*;
+use crate::actions::delete;
+use crate::actions::find::*;
+use crate::actions::insert::*;
+use crate::actions::update::*;
+
+use crate::dtos::{
+    create_admin_key_dto, get_admin_keys_dto, recover_voting_dto, rotate_admin_key_dto,
+    set_recovery_secret_dto,
+};
+use crate::types::AdminScope;
+use crate::utils::{generate_uuid, hash_string, AuthenticatedUser, ErrorResponse};
+use crate::validators::{validate_admin_key_id, validate_admin_key_label, validate_voting_id};
+
+use diesel::prelude::*;
+use diesel::result::Error;
+use rocket::http::Status;
+use rocket_contrib::json::Json;
+
+/// Label of the admin key a recovery issues.
+const RECOVERY_ADMIN_KEY_LABEL: &str = "Recovery";
+
+#[get("/votings/<voting_id>/admin-keys", format = "json")]
+pub fn get_admin_keys(
+    conn: DbConn,
+    voting_id: String,
+    user: AuthenticatedUser,
+) -> Result<Json<Vec<get_admin_keys_dto::GetAdminKeyResponse>>, ErrorResponse> {
+    validate_voting_id(&voting_id)?;
+
+    let voting = find_voting(&conn, &voting_id)
+        .and_then(|voting| check_if_voting_admin(&conn, voting, &user, AdminScope::Full))?;
+
+    Ok(Json(
+        find_admin_keys(&conn, &voting.id)?
+            .into_iter()
+            .map(|admin_key| get_admin_keys_dto::GetAdminKeyResponse {
+                admin_key_id: admin_key.id,
+                label: admin_key.label,
+                scope: admin_key.scope,
+                created_at: admin_key.created_at,
+            })
+            .collect(),
+    ))
+}
+
+#[post("/votings/<voting_id>/admin-keys", format = "json", data = "<input>")]
+pub fn create_admin_key(
+    conn: DbConn,
+    voting_id: String,
+    input: Json<create_admin_key_dto::CreateAdminKeyRequest>,
+    user: AuthenticatedUser,
+) -> Result<Json<create_admin_key_dto::CreateAdminKeyResponse>, ErrorResponse> {
+    validate_voting_id(&voting_id)?;
+    validate_admin_key_label(&input.label)?;
+
+    let voting = find_voting(&conn, &voting_id)
+        .and_then(|voting| check_if_voting_admin(&conn, voting, &user, AdminScope::Full))
+        .and_then(check_if_not_archived)?;
+
+    let admin_key = generate_uuid();
+    let admin_key_id = insert_admin_key(
+        &conn,
+        &voting.id,
+        &hash_string(&admin_key),
+        &input.label,
+        input.scope,
+    )
+    .map_err(|err| {
+        let error_msg = format!(
+            "Could not insert admin key for voting with id: {}",
+            voting.id
+        );
+        println!("{}. err: {:?}", error_msg, err);
+        ErrorResponse {
+            reason: error_msg,
+            status: Status::InternalServerError,
+        }
+    })?;
+
+    Ok(Json(create_admin_key_dto::CreateAdminKeyResponse {
+        admin_key_id,
+        admin_key,
+    }))
+}
+
+/// Revokes the admin key. The last `FULL` admin key of a voting can not be
+/// deleted.
+#[delete("/votings/<voting_id>/admin-keys/<admin_key_id>", format = "json")]
+pub fn delete_admin_key(
+    conn: DbConn,
+    voting_id: String,
+    admin_key_id: String,
+    user: AuthenticatedUser,
+) -> Result<Json<()>, ErrorResponse> {
+    validate_voting_id(&voting_id)?;
+    validate_admin_key_id(&admin_key_id)?;
+
+    let voting = find_voting(&conn, &voting_id)
+        .and_then(|voting| check_if_voting_admin(&conn, voting, &user, AdminScope::Full))
+        .and_then(check_if_not_archived)?;
+    let admin_key = find_voting_admin_key(&conn, &voting, &admin_key_id)?;
+
+    let full_admin_keys = find_admin_keys(&conn, &voting.id)?
+        .iter()
+        .filter(|admin_key| admin_key.scope == AdminScope::Full)
+        .count();
+    if admin_key.scope == AdminScope::Full && full_admin_keys <= 1 {
+        return Err(ErrorResponse {
+            reason: format!(
+                "Admin key with id: {} is the last FULL admin key of voting with id: {}",
+                admin_key.id, voting.id
+            ),
+            status: Status::BadRequest,
+        });
+    }
+
+    delete::delete_admin_key(&conn, &admin_key)?;
+    Ok(Json(()))
+}
+
+/// Replaces the admin key used for the request, the old key stops working
+/// immediately.
+#[post("/votings/<voting_id>/admin-keys/rotate", format = "json")]
+pub fn rotate_admin_key(
+    conn: DbConn,
+    voting_id: String,
+    user: AuthenticatedUser,
+) -> Result<Json<rotate_admin_key_dto::RotateAdminKeyResponse>, ErrorResponse> {
+    validate_voting_id(&voting_id)?;
+
+    let voting = find_voting(&conn, &voting_id).and_then(check_if_not_archived)?;
+    let admin_key = match find_admin_key(&conn, &voting.id, &user)? {
+        Some(admin_key) => admin_key,
+        None => {
+            return Err(ErrorResponse {
+                reason: format!("Admin key is not correct for voting with id: {}", voting.id),
+                status: Status::Unauthorized,
+            })
+        }
+    };
+
+    let new_admin_key = generate_uuid();
+    update_admin_key_hash(&conn, &admin_key, &hash_string(&new_admin_key))?;
+
+    Ok(Json(rotate_admin_key_dto::RotateAdminKeyResponse {
+        admin_key_id: admin_key.id,
+        admin_key: new_admin_key,
+    }))
+}
+
+/// Sets a new recovery secret, the previous one stops working. Replacing a
+/// recovery secret needs the current one besides a `FULL` admin key.
+#[post(
+    "/votings/<voting_id>/recovery-secret",
+    format = "json",
+    data = "<input>"
+)]
+pub fn set_recovery_secret(
+    conn: DbConn,
+    voting_id: String,
+    input: Json<set_recovery_secret_dto::SetRecoverySecretRequest>,
+    user: AuthenticatedUser,
+) -> Result<Json<set_recovery_secret_dto::SetRecoverySecretResponse>, ErrorResponse> {
+    validate_voting_id(&voting_id)?;
+
+    let voting = find_voting(&conn, &voting_id)
+        .and_then(|voting| check_if_voting_admin(&conn, voting, &user, AdminScope::Full))?;
+    if let Some(recovery_secret_hash) = &voting.recovery_secret_hash {
+        let current_recovery_secret_hash = input.current_recovery_secret.as_ref().map(hash_string);
+        if current_recovery_secret_hash.as_ref() != Some(recovery_secret_hash) {
+            return Err(ErrorResponse {
+                reason: format!(
+                    "Recovery secret is not correct for voting with id: {}",
+                    voting.id
+                ),
+                status: Status::Unauthorized,
+            });
+        }
+    }
+
+    let recovery_secret = generate_uuid();
+    update_voting_recovery_secret_hash(&conn, &voting, &hash_string(&recovery_secret)).map_err(
+        |err| {
+            let error_msg = format!(
+                "Could not set recovery secret for voting with id: {}",
+                voting.id
+            );
+            println!("{}. err: {:?}", error_msg, err);
+            ErrorResponse {
+                reason: error_msg,
+                status: Status::InternalServerError,
+            }
+        },
+    )?;
+
+    Ok(Json(set_recovery_secret_dto::SetRecoverySecretResponse {
+        recovery_secret,
+    }))
+}
+
+/// Authenticated with the recovery secret instead of an admin key. Issues a
+/// new `FULL` admin key and deletes every other admin key of any scope, so a
+/// leaked key can not take the voting back. The recovery secret can only be used
+/// once and is replaced.
+#[post("/votings/<voting_id>/recover", format = "json")]
+pub fn recover_voting(
+    conn: DbConn,
+    voting_id: String,
+    user: AuthenticatedUser,
+) -> Result<Json<recover_voting_dto::RecoverVotingResponse>, ErrorResponse> {
+    validate_voting_id(&voting_id)?;
+
+    let voting = find_voting(&conn, &voting_id)
+        .and_then(|voting| check_if_recovery_secret(voting, &user))?;
+
+    let admin_key = generate_uuid();
+    let recovery_secret = generate_uuid();
+    let admin_key_id = conn
+        .transaction::<_, Error, _>(|| {
+            delete::delete_admin_keys(&conn, &voting)?;
+            let admin_key_id = insert_admin_key(
+                &conn,
+                &voting.id,
+                &hash_string(&admin_key),
+                &RECOVERY_ADMIN_KEY_LABEL.to_string(),
+                AdminScope::Full,
+            )?;
+            update_voting_recovery_secret_hash(&conn, &voting, &hash_string(&recovery_secret))?;
+            Ok(admin_key_id)
+        })
+        .map_err(|err| {
+            let error_msg = format!("Could not recover voting with id: {}", voting.id);
+            println!("{}. err: {:?}", error_msg, err);
+            ErrorResponse {
+                reason: error_msg,
+                status: Status::InternalServerError,
+            }
+        })?;
+
+    Ok(Json(recover_voting_dto::RecoverVotingResponse {
+        admin_key_id,
+        admin_key,
+        recovery_secret,
+    }))
+}
@@ -0,0 +1,73 @@
+use crate::pool::DbConn;
+
+use crate::actions::check::*;
+use crate::actions::find::*;
+use crate::actions::update::*;
+
+use crate::models::Voting;
+use crate::routes::poll::change_active_poll;
+use crate::types::AdminScope;
+use crate::utils::{AuthenticatedUser, ErrorResponse};
+use crate::validators::validate_voting_id;
+
+use rocket::http::Status;
+use rocket_contrib::json::Json;
+
+/// Starts the agenda at the first poll when it was not started yet.
+#[post("/votings/<voting_id>/agenda/next", format = "json")]
+pub fn next_agenda_poll(
+    conn: DbConn,
+    voting_id: String,
+    user: AuthenticatedUser,
+) -> Result<Json<()>, ErrorResponse> {
+    validate_voting_id(&voting_id)?;
+
+    let voting = find_voting(&conn, &voting_id)
+        .and_then(|voting| check_if_voting_admin(&conn, voting, &user, AdminScope::Agenda))
+        .and_then(check_if_not_archived)?;
+    let poll_index = match voting.agenda_poll_index {
+        Some(agenda_poll_index) => agenda_poll_index + 1,
+        None => 0,
+    };
+
+    move_agenda(&conn, &voting, poll_index).map(Json)
+}
+
+#[post("/votings/<voting_id>/agenda/previous", format = "json")]
+pub fn previous_agenda_poll(
+    conn: DbConn,
+    voting_id: String,
+    user: AuthenticatedUser,
+) -> Result<Json<()>, ErrorResponse> {
+    validate_voting_id(&voting_id)?;
+
+    let voting = find_voting(&conn, &voting_id)
+        .and_then(|voting| check_if_voting_admin(&conn, voting, &user, AdminScope::Agenda))
+        .and_then(check_if_not_archived)?;
+    let poll_index = match voting.agenda_poll_index {
+        Some(agenda_poll_index) if agenda_poll_index > 0 => agenda_poll_index - 1,
+        _ => {
+            return Err(ErrorResponse {
+                reason: "The agenda has no poll before the current one".to_string(),
+                status: Status::BadRequest,
+            })
+        }
+    };
+
+    move_agenda(&conn, &voting, poll_index).map(Json)
+}
+
+/// Makes the poll at `poll_index` the active poll and the current position of
+/// the agenda.
+pub fn move_agenda(conn: &DbConn, voting: &Voting, poll_index: i32) -> Result<(), ErrorResponse> {
+    let amount_of_polls = find_amount_of_polls(conn, voting)?;
+    if poll_index < 0 || poll_index >= amount_of_polls {
+        return Err(ErrorResponse {
+            reason: format!("The agenda has no poll at index: {}", poll_index),
+            status: Status::BadRequest,
+        });
+    }
+
+    change_active_poll(conn, voting, Some(poll_index))?;
+    update_voting_agenda_poll_index(conn, voting, Some(poll_index))
+}
@@ -0,0 +1,147 @@
+use crate::pool::DbConn;
+
+use crate::actions::check::*;
+use crate::actions::find::*;
+use crate::actions::insert::*;
+use crate::actions::update::*;
+
+use crate::dtos::get_attendance_dto;
+use crate::models::{Voter, Voting};
+use crate::types::AdminScope;
+use crate::utils::{AuthenticatedUser, ErrorResponse};
+use crate::validators::{validate_voter_id, validate_voting_id};
+
+use chrono::Utc;
+use rocket::http::Status;
+use rocket_contrib::json::Json;
+
+#[get("/votings/<voting_id>/attendance", format = "json")]
+pub fn get_attendance(
+    conn: DbConn,
+    voting_id: String,
+    user: AuthenticatedUser,
+) -> Result<Json<get_attendance_dto::GetAttendanceResponse>, ErrorResponse> {
+    validate_voting_id(&voting_id)?;
+
+    let voting = find_voting(&conn, &voting_id)
+        .and_then(|voting| check_if_voting_admin(&conn, voting, &user, AdminScope::Voters))?;
+    let present_voters = find_present_voters(&conn, &voting.id, Utc::now().naive_utc())?;
+    let voters = find_voters(&conn, &voting.id)?;
+
+    let timeline = find_attendances(&conn, &voting.id)?
+        .into_iter()
+        .map(
+            |attendance| get_attendance_dto::GetAttendanceEntryResponse {
+                username: voters
+                    .iter()
+                    .find(|voter| voter.id == attendance.voter_fk)
+                    .map(|voter| voter.username.to_string())
+                    .unwrap_or_default(),
+                voter_id: attendance.voter_fk,
+                checked_in_at: attendance.checked_in_at,
+                checked_out_at: attendance.checked_out_at,
+            },
+        )
+        .collect();
+
+    Ok(Json(get_attendance_dto::GetAttendanceResponse {
+        present_voter_count: present_voters.len() as i32,
+        timeline,
+    }))
+}
+
+#[post("/votings/<voting_id>/attendance/check-in", format = "json")]
+pub fn check_in(
+    conn: DbConn,
+    voting_id: String,
+    user: AuthenticatedUser,
+) -> Result<Json<()>, ErrorResponse> {
+    validate_voting_id(&voting_id)?;
+
+    let voting = find_voting(&conn, &voting_id)
+        .and_then(|voting| check_if_voter(&conn, voting, &user))
+        .and_then(check_if_not_archived)?;
+    let voter = find_voter(&conn, &user).and_then(|voter| check_if_voting_voter(&voting, voter))?;
+
+    check_in_voter(&conn, &voting, voter)
+}
+
+#[post("/votings/<voting_id>/attendance/check-out", format = "json")]
+pub fn check_out(
+    conn: DbConn,
+    voting_id: String,
+    user: AuthenticatedUser,
+) -> Result<Json<()>, ErrorResponse> {
+    validate_voting_id(&voting_id)?;
+
+    let voting = find_voting(&conn, &voting_id)
+        .and_then(|voting| check_if_voter(&conn, voting, &user))
+        .and_then(check_if_not_archived)?;
+    let voter = find_voter(&conn, &user).and_then(|voter| check_if_voting_voter(&voting, voter))?;
+
+    check_out_voter(&conn, &voter)
+}
+
+#[post("/votings/<voting_id>/voters/<voter_id>/check-in", format = "json")]
+pub fn check_in_by_admin(
+    conn: DbConn,
+    voting_id: String,
+    voter_id: String,
+    user: AuthenticatedUser,
+) -> Result<Json<()>, ErrorResponse> {
+    validate_voting_id(&voting_id)?;
+    validate_voter_id(&voter_id)?;
+
+    let voting = find_voting(&conn, &voting_id)
+        .and_then(|voting| check_if_voting_admin(&conn, voting, &user, AdminScope::Voters))
+        .and_then(check_if_not_archived)?;
+    let voter = find_voting_voter(&conn, &voting, &voter_id)?;
+
+    check_in_voter(&conn, &voting, voter)
+}
+
+#[post("/votings/<voting_id>/voters/<voter_id>/check-out", format = "json")]
+pub fn check_out_by_admin(
+    conn: DbConn,
+    voting_id: String,
+    voter_id: String,
+    user: AuthenticatedUser,
+) -> Result<Json<()>, ErrorResponse> {
+    validate_voting_id(&voting_id)?;
+    validate_voter_id(&voter_id)?;
+
+    let voting = find_voting(&conn, &voting_id)
+        .and_then(|voting| check_if_voting_admin(&conn, voting, &user, AdminScope::Voters))
+        .and_then(check_if_not_archived)?;
+    let voter = find_voting_voter(&conn, &voting, &voter_id)?;
+
+    check_out_voter(&conn, &voter)
+}
+
+fn check_in_voter(conn: &DbConn, voting: &Voting, voter: Voter) -> Result<Json<()>, ErrorResponse> {
+    let voter = check_if_not_revoked(voter)?;
+    if find_current_attendance(conn, &voter.id)?.is_some() {
+        return Err(ErrorResponse {
+            reason: format!("Voter with id: {} is already checked in", voter.id),
+            status: Status::BadRequest,
+        });
+    }
+
+    insert_attendance(conn, &voting.id, &voter.id, Utc::now().naive_utc())?;
+    Ok(Json(()))
+}
+
+fn check_out_voter(conn: &DbConn, voter: &Voter) -> Result<Json<()>, ErrorResponse> {
+    match find_current_attendance(conn, &voter.id)? {
+        Some(attendance) => {
+            update_attendance_checked_out(conn, &attendance, Utc::now().naive_utc())?
+        }
+        None => {
+            return Err(ErrorResponse {
+                reason: format!("Voter with id: {} is not checked in", voter.id),
+                status: Status::BadRequest,
+            })
+        }
+    };
+    Ok(Json(()))
+}
@@ -0,0 +1,132 @@
+use crate::pool::DbConn;
+
+use crate::actions::check::*;
+use crate::actions::delete;
+use crate::actions::find::*;
+use crate::actions::insert::*;
+
+use crate::dtos::{get_delegations_dto, set_delegation_dto};
+use crate::utils::{AuthenticatedUser, ErrorResponse};
+use crate::validators::{validate_topic, validate_voter_id, validate_voting_id};
+
+use rocket::http::Status;
+use rocket_contrib::json::Json;
+
+#[get("/votings/<voting_id>/delegations", format = "json")]
+pub fn get_delegations(
+    conn: DbConn,
+    voting_id: String,
+    user: AuthenticatedUser,
+) -> Result<Json<Vec<get_delegations_dto::GetDelegationResponse>>, ErrorResponse> {
+    validate_voting_id(&voting_id)?;
+
+    let voting =
+        find_voting(&conn, &voting_id).and_then(|voting| check_if_voter(&conn, voting, &user))?;
+    let voter = find_voter(&conn, &user).and_then(|voter| check_if_voting_voter(&voting, voter))?;
+
+    let mut delegations_response = Vec::new();
+    for delegation in find_voter_delegations(&conn, &voter.id)? {
+        let delegate = find_voting_voter(&conn, &voting, &delegation.delegate_voter_fk)?;
+        delegations_response.push(get_delegations_dto::GetDelegationResponse {
+            topic: delegation.topic,
+            delegate_voter_id: delegate.id,
+            delegate_username: delegate.username,
+        });
+    }
+
+    Ok(Json(delegations_response))
+}
+
+#[post("/votings/<voting_id>/delegations", format = "json", data = "<input>")]
+pub fn set_delegation(
+    conn: DbConn,
+    voting_id: String,
+    input: Json<set_delegation_dto::SetDelegationRequest>,
+    user: AuthenticatedUser,
+) -> Result<Json<()>, ErrorResponse> {
+    validate_voting_id(&voting_id)?;
+    validate_voter_id(&input.delegate_voter_id)?;
+    validate_topic(&input.topic)?;
+
+    let voting = find_voting(&conn, &voting_id)
+        .and_then(|voting| check_if_voter(&conn, voting, &user))
+        .and_then(check_if_not_archived)?;
+    if voting.secret {
+        return Err(ErrorResponse {
+            reason: "Can not delegate in a secret voting".to_string(),
+            status: Status::BadRequest,
+        });
+    }
+    let voter = find_voter(&conn, &user)
+        .and_then(|voter| check_if_voting_voter(&voting, voter))
+        .and_then(check_if_not_revoked)?;
+    let delegate = find_voting_voter(&conn, &voting, &input.delegate_voter_id)
+        .and_then(check_if_not_revoked)?;
+
+    if voter.id == delegate.id {
+        return Err(ErrorResponse {
+            reason: "Voter can not delegate to themselves".to_string(),
+            status: Status::BadRequest,
+        });
+    }
+
+    // Follow the chain starting at the new delegate, it must not lead back to
+    // the voter.
+    let delegations = find_delegations(&conn, &voting.id, &input.topic)?;
+    let mut chain = vec![&voter.id, &delegate.id];
+    while let Some(delegation) = delegations
+        .iter()
+        .find(|delegation| &delegation.voter_fk == chain[chain.len() - 1])
+    {
+        if chain[1..].contains(&&delegation.delegate_voter_fk) {
+            break;
+        }
+        chain.push(&delegation.delegate_voter_fk);
+        if delegation.delegate_voter_fk == voter.id {
+            return Err(ErrorResponse {
+                reason: format!(
+                    "Delegation on topic: {} would create the cycle: {}",
+                    input.topic,
+                    chain
+                        .iter()
+                        .map(|voter_id| voter_id.to_string())
+                        .collect::<Vec<String>>()
+                        .join(" -> ")
+                ),
+                status: Status::BadRequest,
+            });
+        }
+    }
+
+    insert_delegation(&conn, &voting.id, &voter.id, &delegate.id, &input.topic)?;
+    Ok(Json(()))
+}
+
+#[delete("/votings/<voting_id>/delegations/<topic>", format = "json")]
+pub fn delete_delegation(
+    conn: DbConn,
+    voting_id: String,
+    topic: String,
+    user: AuthenticatedUser,
+) -> Result<Json<()>, ErrorResponse> {
+    validate_voting_id(&voting_id)?;
+
+    let voting = find_voting(&conn, &voting_id)
+        .and_then(|voting| check_if_voter(&conn, voting, &user))
+        .and_then(check_if_not_archived)?;
+    let voter = find_voter(&conn, &user).and_then(|voter| check_if_voting_voter(&voting, voter))?;
+
+    match find_voter_delegations(&conn, &voter.id)?
+        .into_iter()
+        .find(|delegation| delegation.topic == topic)
+    {
+        Some(delegation) => delete::delete_delegation(&conn, &delegation)?,
+        None => {
+            return Err(ErrorResponse {
+                reason: format!("Voter has no delegation on topic: {}", topic),
+                status: Status::NotFound,
+            })
+        }
+    };
+    Ok(Json(()))
+}
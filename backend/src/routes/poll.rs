@@ -1,17 +1,272 @@
 use crate::pool::DbConn;
 
 use crate::actions::check::*;
+use crate::actions::delete::*;
 use crate::actions::find::*;
 use crate::actions::update::*;
 
-use crate::dtos::{get_active_poll_dto, set_active_poll_dto};
+use crate::dtos::{
+    create_poll_dto, create_voting_dto, get_active_poll_dto, set_active_poll_dto,
+    set_poll_order_dto, set_poll_schedule_dto, update_poll_dto,
+};
+use crate::routes::voting::{finalize_poll, insert_poll_with_options};
+use crate::scheduler::apply_schedule;
 use crate::utils::{AuthenticatedUser, ErrorResponse};
-use crate::validators::validate_voting_id;
+use crate::validators::{
+    validate_create_poll_request, validate_poll_description, validate_poll_id, validate_poll_name,
+    validate_poll_schedule, validate_poll_vote_counting, validate_voting_id,
+};
 
-use crate::models::Vote;
+use crate::models::{Poll, PollOption, VoteChoice, Voter, Voting};
+use crate::types::{AdminScope, PollKey, PollState, PollType};
+
+use chrono::Utc;
+use diesel::prelude::*;
+use diesel::result::Error;
 use rocket::http::Status;
 use rocket_contrib::json::Json;
+use std::collections::HashSet;
+
+/// Appends a poll at the end of the agenda.
+#[post("/votings/<voting_id>/polls", format = "json", data = "<input>")]
+pub fn create_poll(
+    conn: DbConn,
+    voting_id: String,
+    input: Json<create_voting_dto::CreateVotingPollRequest>,
+    user: AuthenticatedUser,
+) -> Result<Json<create_poll_dto::CreatePollResponse>, ErrorResponse> {
+    validate_voting_id(&voting_id)?;
+    validate_create_poll_request(&input)?;
+
+    let voting = find_voting(&conn, &voting_id)
+        .and_then(|voting| check_if_voting_admin(&conn, voting, &user, AdminScope::Full))
+        .and_then(check_if_not_archived)?;
+    validate_poll_vote_counting(voting.secret, &input)?;
+    let polls = find_polls(&conn, &voting_id)?;
+    if polls.len() >= 100 {
+        return Err(ErrorResponse {
+            reason: "Voting must have between 1 and 100 polls".to_string(),
+            status: Status::BadRequest,
+        });
+    }
+    let sequenz_number = match polls.last() {
+        Some(poll) => poll.sequenz_number + 10,
+        None => 0,
+    };
+
+    let poll_id = conn
+        .transaction::<_, Error, _>(|| {
+            insert_poll_with_options(&conn, &voting.id, sequenz_number, &input)
+        })
+        .map_err(|err| {
+            let error_msg = format!("Could not insert poll for voting with id: {}", &voting.id);
+            println!("{}. err: {:?}", error_msg, err);
+            ErrorResponse {
+                reason: error_msg,
+                status: Status::InternalServerError,
+            }
+        })?;
+
+    apply_schedule(&conn, &voting)?;
+    Ok(Json(create_poll_dto::CreatePollResponse {
+        poll_id,
+        poll_index: polls.len() as i32,
+    }))
+}
+
+#[put(
+    "/votings/<voting_id>/polls/<poll_index>",
+    format = "json",
+    data = "<input>"
+)]
+pub fn update_poll(
+    conn: DbConn,
+    voting_id: String,
+    poll_index: i32,
+    input: Json<update_poll_dto::UpdatePollRequest>,
+    user: AuthenticatedUser,
+) -> Result<Json<()>, ErrorResponse> {
+    edit_poll(
+        &conn,
+        &voting_id,
+        &PollKey::Index(poll_index),
+        &input,
+        &user,
+    )
+}
+
+#[put(
+    "/votings/<voting_id>/polls/<poll_id>",
+    format = "json",
+    data = "<input>",
+    rank = 2
+)]
+pub fn update_poll_by_id(
+    conn: DbConn,
+    voting_id: String,
+    poll_id: String,
+    input: Json<update_poll_dto::UpdatePollRequest>,
+    user: AuthenticatedUser,
+) -> Result<Json<()>, ErrorResponse> {
+    validate_poll_id(&poll_id)?;
+    edit_poll(&conn, &voting_id, &PollKey::Id(poll_id), &input, &user)
+}
+
+fn edit_poll(
+    conn: &DbConn,
+    voting_id: &String,
+    poll_key: &PollKey,
+    input: &update_poll_dto::UpdatePollRequest,
+    user: &AuthenticatedUser,
+) -> Result<Json<()>, ErrorResponse> {
+    validate_voting_id(voting_id)?;
+    if let Some(name) = &input.name {
+        validate_poll_name(name)?;
+    }
+    if let Some(description) = &input.description {
+        validate_poll_description(description)?;
+    }
+
+    let voting = find_voting(conn, voting_id)
+        .and_then(|voting| check_if_voting_admin(conn, voting, user, AdminScope::Full))
+        .and_then(check_if_not_archived)?;
+    let poll = find_poll_by_key(conn, &voting, poll_key)?;
+
+    update_poll_texts(
+        conn,
+        &poll,
+        input.name.as_ref().unwrap_or(&poll.name),
+        input.description.as_ref().unwrap_or(&poll.description),
+    )
+    .map(Json)
+}
+
+#[delete("/votings/<voting_id>/polls/<poll_index>", format = "json")]
+pub fn delete_poll_at_index(
+    conn: DbConn,
+    voting_id: String,
+    poll_index: i32,
+    user: AuthenticatedUser,
+) -> Result<Json<()>, ErrorResponse> {
+    remove_poll(&conn, &voting_id, &PollKey::Index(poll_index), &user)
+}
+
+#[delete("/votings/<voting_id>/polls/<poll_id>", format = "json", rank = 2)]
+pub fn delete_poll_by_id(
+    conn: DbConn,
+    voting_id: String,
+    poll_id: String,
+    user: AuthenticatedUser,
+) -> Result<Json<()>, ErrorResponse> {
+    validate_poll_id(&poll_id)?;
+    remove_poll(&conn, &voting_id, &PollKey::Id(poll_id), &user)
+}
+
+/// Only polls nobody voted on can be deleted. The agenda stays on the poll
+/// it was on, or moves back to the previous poll if that one is deleted.
+fn remove_poll(
+    conn: &DbConn,
+    voting_id: &String,
+    poll_key: &PollKey,
+    user: &AuthenticatedUser,
+) -> Result<Json<()>, ErrorResponse> {
+    validate_voting_id(voting_id)?;
+
+    let voting = find_voting(conn, voting_id)
+        .and_then(|voting| check_if_voting_admin(conn, voting, user, AdminScope::Full))
+        .and_then(check_if_not_archived)?;
+    let poll = find_poll_by_key(conn, &voting, poll_key)?;
+
+    if find_amount_of_polls(conn, &voting)? <= 1 {
+        return Err(ErrorResponse {
+            reason: "Can not delete the last poll of a voting".to_string(),
+            status: Status::BadRequest,
+        });
+    }
+    if find_amount_of_participations(conn, &poll.id)? > 0 {
+        return Err(ErrorResponse {
+            reason: format!(
+                "Can not delete the poll with {} because it has votes",
+                poll_key
+            ),
+            status: Status::BadRequest,
+        });
+    }
 
+    let poll_index = find_poll_index(conn, &poll)?;
+    delete_poll(conn, &poll)?;
+    match voting.agenda_poll_index {
+        Some(agenda_poll_index) if agenda_poll_index >= poll_index => {
+            update_voting_agenda_poll_index(
+                conn,
+                &voting,
+                Some(agenda_poll_index - 1).filter(|poll_index| *poll_index >= 0),
+            )
+        }
+        _ => Ok(()),
+    }
+    .map(Json)
+}
+
+/// The agenda stays on the poll it was on.
+#[post("/votings/<voting_id>/polls/order", format = "json", data = "<input>")]
+pub fn set_poll_order(
+    conn: DbConn,
+    voting_id: String,
+    input: Json<set_poll_order_dto::SetPollOrderRequest>,
+    user: AuthenticatedUser,
+) -> Result<Json<()>, ErrorResponse> {
+    validate_voting_id(&voting_id)?;
+
+    let voting = find_voting(&conn, &voting_id)
+        .and_then(|voting| check_if_voting_admin(&conn, voting, &user, AdminScope::Full))
+        .and_then(check_if_not_archived)?;
+    let polls = find_polls(&conn, &voting_id)?;
+
+    let poll_ids: HashSet<&String> = polls.iter().map(|poll| &poll.id).collect();
+    let ordered_poll_ids: HashSet<&String> = input.poll_ids.iter().collect();
+    if input.poll_ids.len() != polls.len() || ordered_poll_ids != poll_ids {
+        return Err(ErrorResponse {
+            reason: "The poll order must contain every poll of the voting once".to_string(),
+            status: Status::BadRequest,
+        });
+    }
+
+    conn.transaction::<_, Error, _>(|| {
+        for (poll_index, poll_id) in input.poll_ids.iter().enumerate() {
+            if let Some(poll) = polls.iter().find(|poll| &poll.id == poll_id) {
+                update_poll_sequenz_number(&conn, poll, (poll_index * 10) as i32)?;
+            }
+        }
+        Ok(())
+    })
+    .map_err(|err| {
+        let error_msg = format!("Could not reorder polls of voting with id: {}", &voting.id);
+        println!("{}. err: {:?}", error_msg, err);
+        ErrorResponse {
+            reason: error_msg,
+            status: Status::InternalServerError,
+        }
+    })?;
+
+    match voting
+        .agenda_poll_index
+        .and_then(|agenda_poll_index| polls.get(agenda_poll_index as usize))
+    {
+        Some(agenda_poll) => {
+            let poll_index = input
+                .poll_ids
+                .iter()
+                .position(|poll_id| poll_id == &agenda_poll.id)
+                .map(|poll_index| poll_index as i32);
+            update_voting_agenda_poll_index(&conn, &voting, poll_index)
+        }
+        None => Ok(()),
+    }
+    .map(Json)
+}
+
+/// Polls are set active by `pollId`, or by `pollIndex` for compatibility.
 #[post("/votings/<voting_id>/polls/active", format = "json", data = "<input>")]
 pub fn set_active_poll(
     conn: DbConn,
@@ -21,12 +276,19 @@ pub fn set_active_poll(
 ) -> Result<Json<()>, ErrorResponse> {
     validate_voting_id(&voting_id)?;
 
-    let voting =
-        find_voting(&conn, &voting_id).and_then(|voting| check_if_voting_admin(voting, &user))?;
+    let voting = find_voting(&conn, &voting_id)
+        .and_then(|voting| check_if_voting_admin(&conn, voting, &user, AdminScope::Agenda))
+        .and_then(check_if_not_archived)?;
     let amount_of_polls = find_amount_of_polls(&conn, &voting)?;
 
-    let poll_index = match input.poll_index {
-        Some(poll_index) => {
+    let poll_index = match (input.poll_index, &input.poll_id) {
+        (Some(_), Some(_)) => {
+            return Err(ErrorResponse {
+                reason: "Only one of pollIndex and pollId can be set".to_string(),
+                status: Status::BadRequest,
+            })
+        }
+        (Some(poll_index), None) => {
             if amount_of_polls <= poll_index as i32 {
                 return Err(ErrorResponse {
                     reason: "Can not set active poll index larger than the amount of polls"
@@ -36,60 +298,431 @@ pub fn set_active_poll(
             }
             Some(poll_index as i32)
         }
-        None => None,
+        (None, Some(poll_id)) => {
+            validate_poll_id(poll_id)?;
+            let poll = find_poll(&conn, &voting, poll_id)?;
+            Some(find_poll_index(&conn, &poll)?)
+        }
+        (None, None) => None,
     };
 
-    update_voting_active_poll_index(&conn, &voting, &poll_index)?;
+    change_active_poll(&conn, &voting, poll_index)?;
+    if poll_index.is_some() {
+        update_voting_agenda_poll_index(&conn, &voting, poll_index)?;
+    }
     Ok(Json(()))
 }
 
+#[post("/votings/<voting_id>/polls/<poll_index>/open", format = "json")]
+pub fn open_poll(
+    conn: DbConn,
+    voting_id: String,
+    poll_index: i32,
+    user: AuthenticatedUser,
+) -> Result<Json<()>, ErrorResponse> {
+    change_poll_state(
+        &conn,
+        &voting_id,
+        &PollKey::Index(poll_index),
+        &user,
+        PollState::Draft,
+        PollState::Open,
+    )
+}
+
+#[post("/votings/<voting_id>/polls/<poll_id>/open", format = "json", rank = 2)]
+pub fn open_poll_by_id(
+    conn: DbConn,
+    voting_id: String,
+    poll_id: String,
+    user: AuthenticatedUser,
+) -> Result<Json<()>, ErrorResponse> {
+    validate_poll_id(&poll_id)?;
+    change_poll_state(
+        &conn,
+        &voting_id,
+        &PollKey::Id(poll_id),
+        &user,
+        PollState::Draft,
+        PollState::Open,
+    )
+}
+
+#[post("/votings/<voting_id>/polls/<poll_index>/close", format = "json")]
+pub fn close_poll(
+    conn: DbConn,
+    voting_id: String,
+    poll_index: i32,
+    user: AuthenticatedUser,
+) -> Result<Json<()>, ErrorResponse> {
+    change_poll_state(
+        &conn,
+        &voting_id,
+        &PollKey::Index(poll_index),
+        &user,
+        PollState::Open,
+        PollState::Closed,
+    )
+}
+
+#[post(
+    "/votings/<voting_id>/polls/<poll_id>/close",
+    format = "json",
+    rank = 2
+)]
+pub fn close_poll_by_id(
+    conn: DbConn,
+    voting_id: String,
+    poll_id: String,
+    user: AuthenticatedUser,
+) -> Result<Json<()>, ErrorResponse> {
+    validate_poll_id(&poll_id)?;
+    change_poll_state(
+        &conn,
+        &voting_id,
+        &PollKey::Id(poll_id),
+        &user,
+        PollState::Open,
+        PollState::Closed,
+    )
+}
+
+#[post("/votings/<voting_id>/polls/<poll_index>/reopen", format = "json")]
+pub fn reopen_poll(
+    conn: DbConn,
+    voting_id: String,
+    poll_index: i32,
+    user: AuthenticatedUser,
+) -> Result<Json<()>, ErrorResponse> {
+    change_poll_state(
+        &conn,
+        &voting_id,
+        &PollKey::Index(poll_index),
+        &user,
+        PollState::Closed,
+        PollState::Open,
+    )
+}
+
+#[post(
+    "/votings/<voting_id>/polls/<poll_id>/reopen",
+    format = "json",
+    rank = 2
+)]
+pub fn reopen_poll_by_id(
+    conn: DbConn,
+    voting_id: String,
+    poll_id: String,
+    user: AuthenticatedUser,
+) -> Result<Json<()>, ErrorResponse> {
+    validate_poll_id(&poll_id)?;
+    change_poll_state(
+        &conn,
+        &voting_id,
+        &PollKey::Id(poll_id),
+        &user,
+        PollState::Closed,
+        PollState::Open,
+    )
+}
+
+#[post(
+    "/votings/<voting_id>/polls/<poll_index>/schedule",
+    format = "json",
+    data = "<input>"
+)]
+pub fn set_poll_schedule(
+    conn: DbConn,
+    voting_id: String,
+    poll_index: i32,
+    input: Json<set_poll_schedule_dto::SetPollScheduleRequest>,
+    user: AuthenticatedUser,
+) -> Result<Json<()>, ErrorResponse> {
+    schedule_poll(
+        &conn,
+        &voting_id,
+        &PollKey::Index(poll_index),
+        &input,
+        &user,
+    )
+}
+
+#[post(
+    "/votings/<voting_id>/polls/<poll_id>/schedule",
+    format = "json",
+    data = "<input>",
+    rank = 2
+)]
+pub fn set_poll_schedule_by_id(
+    conn: DbConn,
+    voting_id: String,
+    poll_id: String,
+    input: Json<set_poll_schedule_dto::SetPollScheduleRequest>,
+    user: AuthenticatedUser,
+) -> Result<Json<()>, ErrorResponse> {
+    validate_poll_id(&poll_id)?;
+    schedule_poll(&conn, &voting_id, &PollKey::Id(poll_id), &input, &user)
+}
+
+fn schedule_poll(
+    conn: &DbConn,
+    voting_id: &String,
+    poll_key: &PollKey,
+    input: &set_poll_schedule_dto::SetPollScheduleRequest,
+    user: &AuthenticatedUser,
+) -> Result<Json<()>, ErrorResponse> {
+    validate_voting_id(voting_id)?;
+    validate_poll_schedule(&input.opens_at, &input.closes_at)?;
+
+    let voting = find_voting(conn, voting_id)
+        .and_then(|voting| check_if_voting_admin(conn, voting, user, AdminScope::Agenda))
+        .and_then(check_if_not_archived)?;
+    let poll = find_poll_by_key(conn, &voting, poll_key)?;
+
+    update_poll_schedule(conn, &poll, input.opens_at, input.closes_at)?;
+    apply_schedule(conn, &voting).map(Json)
+}
+
+/// Moves a poll that is in state `from` to state `to`.
+fn change_poll_state(
+    conn: &DbConn,
+    voting_id: &String,
+    poll_key: &PollKey,
+    user: &AuthenticatedUser,
+    from: PollState,
+    to: PollState,
+) -> Result<Json<()>, ErrorResponse> {
+    validate_voting_id(voting_id)?;
+
+    let voting = find_voting(conn, voting_id)
+        .and_then(|voting| check_if_voting_admin(conn, voting, user, AdminScope::Agenda))
+        .and_then(check_if_not_archived)?;
+    let poll = find_poll_by_key(conn, &voting, poll_key)?;
+
+    if poll.state != from {
+        return Err(ErrorResponse {
+            reason: format!(
+                "Can not change the poll with {} to {} because it is {}",
+                poll_key,
+                to.as_str(),
+                poll.state.as_str()
+            ),
+            status: Status::BadRequest,
+        });
+    }
+
+    let now = Utc::now().naive_utc();
+    if let (PollState::Open, Some(closes_at)) = (to, poll.closes_at) {
+        if closes_at <= now {
+            return Err(ErrorResponse {
+                reason: format!(
+                    "Can not open the poll with {} because its closesAt passed",
+                    poll_key
+                ),
+                status: Status::BadRequest,
+            });
+        }
+    }
+    match to {
+        PollState::Closed => finalize_poll(conn, &voting, &poll, now),
+        _ => update_poll_state(conn, &poll, to, now),
+    }
+    .map(Json)
+}
+
+/// Compatibility for a single active poll: opens the poll at `poll_index`
+/// if it was never open and closes the poll leaving the current agenda
+/// position. Polls opened by themselves or by their schedule stay open, closed
+/// polls stay closed until they are reopened.
+pub fn change_active_poll(
+    conn: &DbConn,
+    voting: &Voting,
+    poll_index: Option<i32>,
+) -> Result<(), ErrorResponse> {
+    let now = Utc::now().naive_utc();
+
+    for (index, poll) in find_polls(conn, &voting.id)?.iter().enumerate() {
+        let index = Some(index as i32);
+        if index == poll_index {
+            if poll.state == PollState::Draft {
+                update_poll_state(conn, poll, PollState::Open, now)?;
+            }
+        } else if index == voting.agenda_poll_index && poll.state == PollState::Open {
+            finalize_poll(conn, voting, poll, now)?;
+        }
+    }
+    Ok(())
+}
+
+/// Every open poll of the voting.
 #[get("/votings/<voting_id>/polls/active", format = "json")]
 pub fn get_active_poll(
     conn: DbConn,
     voting_id: String,
     user: AuthenticatedUser,
-) -> Result<Json<Option<get_active_poll_dto::GetActivePollResponse>>, ErrorResponse> {
+) -> Result<Json<Vec<get_active_poll_dto::GetActivePollResponse>>, ErrorResponse> {
     validate_voting_id(&voting_id)?;
 
     let voting =
         find_voting(&conn, &voting_id).and_then(|voting| check_if_voter(&conn, voting, &user))?;
+    let voter = find_voter(&conn, &user).and_then(|voter| check_if_voting_voter(&voting, voter))?;
+    apply_schedule(&conn, &voting)?;
 
-    let active_poll_index = match voting.active_poll_index {
-        Some(active_poll_index) => active_poll_index,
-        None => return Ok(Json(None)),
+    let mut active_polls_response = Vec::new();
+    for (poll_index, poll) in find_polls(&conn, &voting_id)?.iter().enumerate() {
+        if poll.state == PollState::Open {
+            active_polls_response.push(get_active_poll_response(
+                &conn,
+                &voting,
+                &voter,
+                poll_index as i32,
+                poll,
+            )?);
+        }
+    }
+
+    Ok(Json(active_polls_response))
+}
+
+fn get_active_poll_response(
+    conn: &DbConn,
+    voting: &Voting,
+    voter: &Voter,
+    poll_index: i32,
+    poll: &Poll,
+) -> Result<get_active_poll_dto::GetActivePollResponse, ErrorResponse> {
+    let options = find_poll_options(conn, &poll.id)?;
+
+    let vote = find_vote(conn, &poll.id, &voter.id)?;
+    let choices = match &vote {
+        Some(vote) => Some(find_vote_choices(conn, &vote.vote_fk)?),
+        None => None,
+    };
+    let voted = find_voted(conn, poll, &options, &voter.id, &choices)?;
+    let vote_changes = find_amount_of_vote_changes(conn, &poll.id, &voter.id)?;
+    let can_change_vote = match voting.max_vote_changes {
+        Some(max_vote_changes) => voting.allow_vote_change && vote_changes < max_vote_changes,
+        None => voting.allow_vote_change,
+    };
+    let voted_scores = match (poll.poll_type, &choices) {
+        (PollType::Score, Some(choices)) => Some(
+            choices
+                .iter()
+                .map(|choice| get_active_poll_dto::GetActivePollScoreResponse {
+                    option_id: choice.poll_option_fk.to_string(),
+                    score: choice.score.unwrap_or(0),
+                })
+                .collect(),
+        ),
+        _ => None,
     };
 
-    let polls = find_polls(&conn, &voting_id)?;
-    if active_poll_index < 0 || active_poll_index >= polls.len() as i32 {
-        println!(
-            "Could not load poll for voting with id: {} and active_poll_index: {}",
-            &voting_id,
-            polls.len()
-        );
-        return Err(ErrorResponse {
-            reason: "Could not load the poll with the current active index".to_string(),
-            status: Status::InternalServerError,
+    let proxy_username = match find_proxy(conn, &voter.id)? {
+        Some(proxy) => Some(find_voter_by_id(conn, &proxy.proxy_voter_fk)?.username),
+        None => None,
+    };
+    let delegate_username = match &poll.topic {
+        Some(topic) => match find_voter_delegations(conn, &voter.id)?
+            .into_iter()
+            .find(|delegation| &delegation.topic == topic)
+        {
+            Some(delegation) => {
+                Some(find_voter_by_id(conn, &delegation.delegate_voter_fk)?.username)
+            }
+            None => None,
+        },
+        None => None,
+    };
+    let mut proxy_ballots = Vec::new();
+    for proxy in find_held_proxies(conn, &voter.id)? {
+        let proxy_ballot_voter = find_voter_by_id(conn, &proxy.voter_fk)?;
+        let proxy_ballot_choices = match find_vote(conn, &poll.id, &proxy_ballot_voter.id)? {
+            Some(vote) => Some(find_vote_choices(conn, &vote.vote_fk)?),
+            None => None,
+        };
+        let voted = find_voted(
+            conn,
+            poll,
+            &options,
+            &proxy_ballot_voter.id,
+            &proxy_ballot_choices,
+        )?;
+        proxy_ballots.push(get_active_poll_dto::GetActivePollBallotResponse {
+            voter_id: proxy_ballot_voter.id,
+            username: proxy_ballot_voter.username,
+            voted,
         });
     }
 
-    let poll = &polls[active_poll_index as usize];
-
-    let voter = find_voter(&conn, &user)?;
-    let voted = find_vote(&conn, &poll.id, &voter.id)?.map(|vote| get_answered_from_vote(&vote));
-
-    Ok(Json(Some(get_active_poll_dto::GetActivePollResponse {
-        poll_index: active_poll_index,
+    Ok(get_active_poll_dto::GetActivePollResponse {
+        poll_id: poll.id.to_string(),
+        poll_index,
         name: (&poll.name).to_string(),
         description: (&poll.description).to_string(),
+        poll_type: poll.poll_type,
+        state: poll.state,
+        closes_at: poll.closes_at,
+        topic: poll.topic.clone(),
+        delegate_username,
+        options: options
+            .iter()
+            .map(|option| get_active_poll_dto::GetActivePollOptionResponse {
+                option_id: option.id.to_string(),
+                name: option.name.to_string(),
+            })
+            .collect(),
+        min_approvals: poll.min_approvals,
+        max_approvals: poll.max_approvals,
+        max_score: poll.max_score,
         voted,
-    })))
+        voted_option_ids: choices.map(|choices| {
+            choices
+                .into_iter()
+                .map(|choice| choice.poll_option_fk)
+                .collect()
+        }),
+        voted_scores,
+        voted_at: vote.map(|vote| vote.voted_at),
+        vote_changes,
+        can_change_vote,
+        proxy_username,
+        proxy_ballots,
+    })
+}
+
+/// Secret ballots can not be found by the voter, only their participation
+/// tells that they voted.
+fn find_voted(
+    conn: &DbConn,
+    poll: &Poll,
+    options: &[PollOption],
+    voter_id: &String,
+    choices: &Option<Vec<VoteChoice>>,
+) -> Result<Option<String>, ErrorResponse> {
+    match choices {
+        Some(choices) => Ok(Some(get_answered_from_choices(
+            poll.poll_type,
+            options,
+            choices,
+        ))),
+        None => Ok(find_participation(conn, &poll.id, voter_id)?.map(|_| "VOTED".to_string())),
+    }
 }
 
-fn get_answered_from_vote(vote: &Vote) -> String {
-    match vote.answer {
-        None => "ABSTAIN",
-        Some(true) => "ACCEPT",
-        Some(false) => "DECLINE",
+fn get_answered_from_choices(
+    poll_type: PollType,
+    options: &[PollOption],
+    choices: &[VoteChoice],
+) -> String {
+    let preset_answer = choices.first().and_then(|choice| {
+        options
+            .iter()
+            .find(|option| option.id == choice.poll_option_fk)
+            .and_then(|option| option.preset_answer)
+    });
+    match (poll_type, preset_answer) {
+        (PollType::YesNo, Some(preset_answer)) => preset_answer.as_str(),
+        _ => "VOTED",
     }
     .to_string()
 }
@@ -0,0 +1,121 @@
+use crate::pool::DbConn;
+
+use crate::actions::check::*;
+use crate::actions::delete;
+use crate::actions::find::*;
+use crate::actions::insert::*;
+use crate::actions::update::*;
+
+use crate::dtos::{create_template_dto, create_voting_dto, get_template_dto};
+use crate::utils::{generate_uuid, hash_string, AuthenticatedUser, ErrorResponse};
+use crate::validators::{validate_template_name, validate_template_voting_request};
+
+use rocket::http::Status;
+use rocket_contrib::json::Json;
+
+#[post("/templates", format = "json", data = "<input>")]
+pub fn create_template(
+    conn: DbConn,
+    input: Json<create_template_dto::CreateTemplateRequest>,
+) -> Result<Json<create_template_dto::CreateTemplateResponse>, ErrorResponse> {
+    validate_template_name(&input.name)?;
+    validate_template_voting_request(&input.voting)?;
+
+    let admin_key = generate_uuid();
+    let admin_key_hash = hash_string(&admin_key);
+
+    insert_voting_template(
+        &conn,
+        &input.name,
+        &admin_key_hash,
+        &to_template_definition(&input.voting)?,
+    )?;
+
+    Ok(Json(create_template_dto::CreateTemplateResponse {
+        name: input.name.to_string(),
+        admin_key,
+    }))
+}
+
+#[get("/templates/<name>", format = "json")]
+pub fn get_template(
+    conn: DbConn,
+    name: String,
+    user: AuthenticatedUser,
+) -> Result<Json<get_template_dto::GetTemplateResponse>, ErrorResponse> {
+    validate_template_name(&name)?;
+
+    let voting = find_template_definition(&conn, &name, &user)?;
+
+    Ok(Json(get_template_dto::GetTemplateResponse { name, voting }))
+}
+
+#[put("/templates/<name>", format = "json", data = "<input>")]
+pub fn update_template(
+    conn: DbConn,
+    name: String,
+    input: Json<create_voting_dto::CreateVotingRequest>,
+    user: AuthenticatedUser,
+) -> Result<Json<()>, ErrorResponse> {
+    validate_template_name(&name)?;
+    validate_template_voting_request(&input)?;
+
+    let template = find_voting_template(&conn, &name)
+        .and_then(|template| check_if_template_admin(template, &user))?;
+
+    update_voting_template_definition(&conn, &template, &to_template_definition(&input)?).map(Json)
+}
+
+#[delete("/templates/<name>", format = "json")]
+pub fn delete_template(
+    conn: DbConn,
+    name: String,
+    user: AuthenticatedUser,
+) -> Result<Json<()>, ErrorResponse> {
+    validate_template_name(&name)?;
+
+    let template = find_voting_template(&conn, &name)
+        .and_then(|template| check_if_template_admin(template, &user))?;
+
+    delete::delete_voting_template(&conn, &template).map(Json)
+}
+
+/// The create voting request stored in the template. Only the admin key of
+/// the template can read it.
+pub fn find_template_definition(
+    conn: &DbConn,
+    name: &String,
+    user: &AuthenticatedUser,
+) -> Result<create_voting_dto::CreateVotingRequest, ErrorResponse> {
+    let template = find_voting_template(conn, name)
+        .and_then(|template| check_if_template_admin(template, user))?;
+
+    serde_json::from_str(&template.definition).map_err(|err| {
+        let error_msg = format!("Could not read template with name: {}", name);
+        println!("{}. err: {:?}", error_msg, err);
+        ErrorResponse {
+            reason: error_msg,
+            status: Status::InternalServerError,
+        }
+    })
+}
+
+/// Poll schedules are points in time that would have passed for later
+/// votings, so the template does not keep them.
+fn to_template_definition(
+    voting: &create_voting_dto::CreateVotingRequest,
+) -> Result<String, ErrorResponse> {
+    let mut voting = voting.clone();
+    for poll in &mut voting.polls {
+        poll.opens_at = None;
+        poll.closes_at = None;
+    }
+    serde_json::to_string(&voting).map_err(|err| {
+        let error_msg = "Could not write template definition".to_string();
+        println!("{}. err: {:?}", error_msg, err);
+        ErrorResponse {
+            reason: error_msg,
+            status: Status::InternalServerError,
+        }
+    })
+}
@@ -3,11 +3,18 @@ use crate::pool::DbConn;
 use crate::actions::check::*;
 use crate::actions::find::*;
 use crate::actions::insert::*;
+use crate::actions::update::*;
 
 use crate::dtos::set_vote_dto;
+use crate::models::{Poll, PollOption, Voter, Voting};
+use crate::scheduler::apply_schedule;
+use crate::types::{PollKey, PollState, PollType, PresetAnswer};
 use crate::utils::{AuthenticatedUser, ErrorResponse};
-use crate::validators::validate_voting_id;
+use crate::validators::{validate_poll_id, validate_voting_id};
 
+use chrono::Utc;
+use diesel::prelude::*;
+use diesel::result::{DatabaseErrorKind, Error, Error::DatabaseError};
 use rocket::http::Status;
 use rocket_contrib::json::Json;
 
@@ -23,41 +30,416 @@ pub fn set_vote(
     input: Json<set_vote_dto::SetVoteRequest>,
     user: AuthenticatedUser,
 ) -> Result<Json<()>, ErrorResponse> {
-    validate_voting_id(&voting_id)?;
+    vote(
+        &conn,
+        &voting_id,
+        &PollKey::Index(poll_index),
+        &input,
+        &user,
+    )
+}
+
+#[post(
+    "/votings/<voting_id>/polls/<poll_id>/vote",
+    format = "json",
+    data = "<input>",
+    rank = 2
+)]
+pub fn set_vote_by_poll_id(
+    conn: DbConn,
+    voting_id: String,
+    poll_id: String,
+    input: Json<set_vote_dto::SetVoteRequest>,
+    user: AuthenticatedUser,
+) -> Result<Json<()>, ErrorResponse> {
+    validate_poll_id(&poll_id)?;
+    vote(&conn, &voting_id, &PollKey::Id(poll_id), &input, &user)
+}
+
+fn vote(
+    conn: &DbConn,
+    voting_id: &String,
+    poll_key: &PollKey,
+    input: &set_vote_dto::SetVoteRequest,
+    user: &AuthenticatedUser,
+) -> Result<Json<()>, ErrorResponse> {
+    validate_voting_id(voting_id)?;
 
-    let voting =
-        find_voting(&conn, &voting_id).and_then(|voting| check_if_voter(&conn, voting, &user))?;
+    let voting = find_voting(conn, voting_id)
+        .and_then(|voting| check_if_voter(conn, voting, user))
+        .and_then(check_if_not_archived)?;
+    apply_schedule(conn, &voting)?;
 
-    if voting.active_poll_index.is_none() {
+    let poll = find_poll_by_key(conn, &voting, poll_key)?;
+    if poll.state != PollState::Open {
         return Err(ErrorResponse {
-            reason: "Can not vote because no vote is active".to_string(),
+            reason: format!(
+                "Can not vote because the poll with {} is {}",
+                poll_key,
+                poll.state.as_str()
+            ),
             status: Status::BadRequest,
         });
     }
-    if voting.active_poll_index.unwrap() != poll_index {
+    let (voter, proxy_voter) = find_ballot_voter(conn, &voting, user, &input.voter_id)?;
+    // Whoever casts the ballot must have been present, for a ballot given to a
+    // proxy that is the proxy holder.
+    if let (true, Some(opened_at)) = (poll.present_voters_only, poll.opened_at) {
+        let casting_voter = proxy_voter.as_ref().unwrap_or(&voter);
+        if !find_present_voters(conn, &voting.id, opened_at)?
+            .iter()
+            .any(|present_voter| present_voter.id == casting_voter.id)
+        {
+            return Err(ErrorResponse {
+                reason: format!(
+                    "Voter with id: {} was not present when the poll with {} opened",
+                    casting_voter.id, poll_key
+                ),
+                status: Status::Forbidden,
+            });
+        }
+    }
+
+    let replaces_vote = match find_participation(conn, &poll.id, &voter.id)? {
+        Some(_) => check_if_vote_changeable(conn, &voting, &poll, poll_key, &voter).map(|_| true),
+        None => Ok(false),
+    }?;
+
+    let options = find_poll_options(conn, &poll.id)?;
+    let choices = get_choices_from_request(&poll, &options, input)?;
+
+    let now = Utc::now().naive_utc();
+    let voted = conn
+        .transaction::<bool, Error, _>(|| {
+            if replaces_vote {
+                // The lock makes a concurrent change of the same ballot wait and
+                // then find no current ballot anymore.
+                let replaced_vote = match find_vote_for_update(conn, &poll.id, &voter.id)? {
+                    Some(vote) => vote,
+                    None => return Ok(false),
+                };
+                if update_vote_replaced(conn, &replaced_vote, now)? == 0 {
+                    return Ok(false);
+                }
+            } else {
+                insert_participation(
+                    conn,
+                    &poll.id,
+                    &voter.id,
+                    proxy_voter.as_ref().map(|proxy_voter| &proxy_voter.id),
+                )?;
+            }
+            let vote_id = insert_vote(conn, &poll.id)?;
+            // A secret ballot keeps no column that leads back to the voter. It
+            // shares the transaction with the participation though, so the
+            // `xmin` of both rows matches, see `CreateVotingRequest::secret`.
+            if !voting.secret {
+                insert_named_vote(conn, &vote_id, &poll.id, &voter, now)?;
+            }
+            for choice in &choices {
+                insert_vote_choice(conn, &vote_id, &choice.option.id, choice.rank, choice.score)?;
+            }
+            Ok(true)
+        })
+        .map_err(|err| match err {
+            DatabaseError(DatabaseErrorKind::UniqueViolation, _) => ErrorResponse {
+                reason: format!(
+                    "Voter with id: {} voted at the same time on the poll with {}",
+                    voter.id, poll_key
+                ),
+                status: Status::Conflict,
+            },
+            err => {
+                let error_msg = format!(
+                    "Could not insert vote for poll id: {} and voter id: {}",
+                    poll.id, voter.id
+                );
+                println!("{}. err: {:?}", error_msg, err);
+                ErrorResponse {
+                    reason: error_msg,
+                    status: Status::InternalServerError,
+                }
+            }
+        })?;
+    if !voted {
         return Err(ErrorResponse {
             reason: format!(
-                "Can not vote because the poll_index: {} is not active",
-                poll_index
+                "The ballot of voter with id: {} was changed at the same time on the poll with {}",
+                voter.id, poll_key
             ),
-            status: Status::BadRequest,
+            status: Status::Conflict,
         });
     }
 
-    let poll = find_poll_at_index(&conn, &voting, poll_index)?;
-    let voter = find_voter(&conn, &user)?;
+    // The vote may complete the poll of an auto-advancing agenda.
+    apply_schedule(conn, &voting).map(Json)
+}
 
-    let voted = find_vote(&conn, &poll.id, &voter.id)?;
-    if voted.is_some() {
+/// Whether the voting allows a voter who already voted to change their ballot
+/// once more.
+fn check_if_vote_changeable(
+    conn: &DbConn,
+    voting: &Voting,
+    poll: &Poll,
+    poll_key: &PollKey,
+    voter: &Voter,
+) -> Result<(), ErrorResponse> {
+    if !voting.allow_vote_change {
         return Err(ErrorResponse {
+            reason: format!("Voter already voted on this poll with {}", poll_key),
+            status: Status::BadRequest,
+        });
+    }
+    if let Some(max_vote_changes) = voting.max_vote_changes {
+        if find_amount_of_vote_changes(conn, &poll.id, &voter.id)? >= max_vote_changes {
+            return Err(ErrorResponse {
+                reason: format!(
+                    "Voter already changed their vote {} times on this poll with {}",
+                    max_vote_changes, poll_key
+                ),
+                status: Status::BadRequest,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// The voter whose ballot is cast and, when it is cast by their proxy, the
+/// authenticated proxy voter.
+fn find_ballot_voter(
+    conn: &DbConn,
+    voting: &Voting,
+    user: &AuthenticatedUser,
+    voter_id: &Option<String>,
+) -> Result<(Voter, Option<Voter>), ErrorResponse> {
+    let authenticated_voter = find_voter(conn, user)
+        .and_then(|voter| check_if_voting_voter(voting, voter))
+        .and_then(check_if_not_revoked)?;
+    match voter_id {
+        Some(voter_id) if voter_id != &authenticated_voter.id => {
+            let voter = find_voting_voter(conn, voting, voter_id).and_then(check_if_not_revoked)?;
+            match find_proxy(conn, &voter.id)? {
+                Some(proxy) if proxy.proxy_voter_fk == authenticated_voter.id => {
+                    Ok((voter, Some(authenticated_voter)))
+                }
+                _ => Err(ErrorResponse {
+                    reason: format!(
+                        "Voter does not hold the ballot of voter with id: {}",
+                        voter.id
+                    ),
+                    status: Status::Forbidden,
+                }),
+            }
+        }
+        _ => match find_proxy(conn, &authenticated_voter.id)? {
+            Some(_) => Err(ErrorResponse {
+                reason: "Can not vote because the ballot was given to a proxy".to_string(),
+                status: Status::BadRequest,
+            }),
+            None => Ok((authenticated_voter, None)),
+        },
+    }
+}
+
+/// A validated choice of a ballot. Choices of unordered ballots all have
+/// rank 0.
+struct Choice<'a> {
+    option: &'a PollOption,
+    rank: i32,
+    score: Option<i32>,
+}
+
+fn get_choices_from_request<'a>(
+    poll: &Poll,
+    options: &'a [PollOption],
+    input: &set_vote_dto::SetVoteRequest,
+) -> Result<Vec<Choice<'a>>, ErrorResponse> {
+    validate_request_fields(poll, input)?;
+
+    match poll.poll_type {
+        PollType::YesNo | PollType::SingleChoice => {
+            let option = match &input.option_id {
+                Some(option_id) => find_option(poll, options, option_id)?,
+                None => find_preset_option(poll, options, &input.answer)?,
+            };
+            Ok(vec![Choice {
+                option,
+                rank: 0,
+                score: None,
+            }])
+        }
+        PollType::InstantRunoff | PollType::Schulze => {
+            let option_ids = match &input.option_ids {
+                Some(option_ids) if !option_ids.is_empty() => option_ids,
+                _ => {
+                    return Err(ErrorResponse {
+                        reason: "Can not vote without optionIds on a ranked poll".to_string(),
+                        status: Status::BadRequest,
+                    })
+                }
+            };
+            let options = find_distinct_options(poll, options, option_ids.iter())?;
+            Ok(options
+                .into_iter()
+                .enumerate()
+                .map(|(rank, option)| Choice {
+                    option,
+                    rank: rank as i32,
+                    score: None,
+                })
+                .collect())
+        }
+        PollType::Approval => {
+            let option_ids = input.option_ids.as_deref().unwrap_or_default();
+            let min_approvals = poll.min_approvals.unwrap_or(1);
+            let max_approvals = poll.max_approvals.unwrap_or(options.len() as i32);
+            let approvals = option_ids.len() as i32;
+            if approvals < min_approvals || approvals > max_approvals {
+                return Err(ErrorResponse {
+                    reason: format!(
+                        "Can not approve {} options, the poll allows between {} and {} approvals",
+                        approvals, min_approvals, max_approvals
+                    ),
+                    status: Status::BadRequest,
+                });
+            }
+            let options = find_distinct_options(poll, options, option_ids.iter())?;
+            Ok(options
+                .into_iter()
+                .map(|option| Choice {
+                    option,
+                    rank: 0,
+                    score: None,
+                })
+                .collect())
+        }
+        PollType::Score => {
+            let scores = input.scores.as_deref().unwrap_or_default();
+            if scores.len() != options.len() {
+                return Err(ErrorResponse {
+                    reason: format!(
+                        "Can not vote with {} scores, every one of the {} options needs a score",
+                        scores.len(),
+                        options.len()
+                    ),
+                    status: Status::BadRequest,
+                });
+            }
+            let max_score = poll.max_score.unwrap_or(5);
+            if let Some(score) = scores
+                .iter()
+                .find(|score| score.score < 0 || score.score > max_score)
+            {
+                return Err(ErrorResponse {
+                    reason: format!("Score: {} is not between 0 and {}", score.score, max_score),
+                    status: Status::BadRequest,
+                });
+            }
+            let options =
+                find_distinct_options(poll, options, scores.iter().map(|score| &score.option_id))?;
+            Ok(options
+                .into_iter()
+                .zip(scores)
+                .map(|(option, score)| Choice {
+                    option,
+                    rank: 0,
+                    score: Some(score.score),
+                })
+                .collect())
+        }
+    }
+}
+
+/// Rejects the fields of the request that do not belong to the poll type.
+fn validate_request_fields(
+    poll: &Poll,
+    input: &set_vote_dto::SetVoteRequest,
+) -> Result<(), ErrorResponse> {
+    let allowed_fields: &[&str] = match poll.poll_type {
+        PollType::YesNo => &["optionId", "answer"],
+        PollType::SingleChoice => &["optionId"],
+        PollType::InstantRunoff | PollType::Schulze | PollType::Approval => &["optionIds"],
+        PollType::Score => &["scores"],
+    };
+    let used_fields = [
+        ("optionId", input.option_id.is_some()),
+        ("optionIds", input.option_ids.is_some()),
+        ("scores", input.scores.is_some()),
+        ("answer", input.answer.is_some()),
+    ];
+    match used_fields
+        .iter()
+        .find(|(field, used)| *used && !allowed_fields.contains(field))
+    {
+        Some((field, _)) => Err(ErrorResponse {
             reason: format!(
-                "Voter already voted on this poll with index: {}",
-                poll_index
+                "Can not vote with {} on a poll of type {}",
+                field,
+                poll.poll_type.as_str()
             ),
             status: Status::BadRequest,
-        });
+        }),
+        None => Ok(()),
+    }
+}
+
+fn find_option<'a>(
+    poll: &Poll,
+    options: &'a [PollOption],
+    option_id: &String,
+) -> Result<&'a PollOption, ErrorResponse> {
+    options
+        .iter()
+        .find(|option| option_id.eq(&option.id))
+        .ok_or_else(|| ErrorResponse {
+            reason: format!(
+                "Option with id: {} does not exist on poll with id: {}",
+                option_id, poll.id
+            ),
+            status: Status::BadRequest,
+        })
+}
+
+fn find_distinct_options<'a, 'b>(
+    poll: &Poll,
+    options: &'a [PollOption],
+    option_ids: impl Iterator<Item = &'b String>,
+) -> Result<Vec<&'a PollOption>, ErrorResponse> {
+    let mut found_options: Vec<&PollOption> = Vec::new();
+    for option_id in option_ids {
+        let option = find_option(poll, options, option_id)?;
+        if found_options.contains(&option) {
+            return Err(ErrorResponse {
+                reason: format!("Option with id: {} was chosen more than once", option_id),
+                status: Status::BadRequest,
+            });
+        }
+        found_options.push(option);
     }
+    Ok(found_options)
+}
 
-    insert_vote(&conn, &poll.id, &voter.id, &input.answer)?;
-    Ok(Json(()))
+fn find_preset_option<'a>(
+    poll: &Poll,
+    options: &'a [PollOption],
+    answer: &Option<bool>,
+) -> Result<&'a PollOption, ErrorResponse> {
+    if poll.poll_type != PollType::YesNo {
+        return Err(ErrorResponse {
+            reason: "Can not vote without an optionId on this poll".to_string(),
+            status: Status::BadRequest,
+        });
+    }
+    let preset_answer = PresetAnswer::from_answer(answer);
+    options
+        .iter()
+        .find(|option| option.preset_answer == Some(preset_answer))
+        .ok_or_else(|| ErrorResponse {
+            reason: format!(
+                "Preset option: {} does not exist on poll with id: {}",
+                preset_answer.as_str(),
+                poll.id
+            ),
+            status: Status::InternalServerError,
+        })
 }
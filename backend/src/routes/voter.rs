@@ -1,14 +1,39 @@
 use crate::pool::DbConn;
 
 use crate::actions::check::*;
+use crate::actions::delete;
 use crate::actions::find::*;
 use crate::actions::insert::*;
+use crate::actions::update::*;
 
-use crate::dtos::{create_voter_dto, get_voter_info_dto};
-use crate::utils::{generate_uuid, hash_string, AuthenticatedUser, ErrorResponse};
-use crate::validators::{validate_create_voter_request, validate_voting_id};
+use crate::csv;
+use crate::dtos::{
+    create_voter_dto, get_roster_dto, get_voter_info_dto, get_voters_dto, import_voters_dto,
+    regenerate_voter_key_dto, set_proxy_dto, update_voter_dto,
+};
+use crate::models::{Poll, VoteChoice, Voter};
+use crate::scheduler::apply_schedule;
+use crate::types::{AdminScope, PollState, TiePolicy};
+use crate::utils::{
+    generate_uuid, hash_string, AuthenticatedUser, CsvAttachment, ErrorResponse, RowError,
+    RowErrorsResponse,
+};
+use crate::validators::{
+    validate_create_voter_request, validate_voter_email, validate_voter_id,
+    validate_voter_username, validate_voter_weight, validate_voting_id,
+};
 
+use chrono::Utc;
+use diesel::prelude::*;
+use diesel::result::Error;
+use rocket::http::Status;
+use rocket::Data;
 use rocket_contrib::json::Json;
+use std::collections::{HashMap, HashSet};
+use std::io::Read;
+
+/// Upper limit for the size of a CSV import.
+const IMPORT_CSV_LIMIT: u64 = 1024 * 1024;
 
 #[post("/votings/<voting_id>/voters", format = "json", data = "<input>")]
 pub fn create_voter(
@@ -23,17 +48,378 @@ pub fn create_voter(
     let voter_key = generate_uuid();
     let voter_key_hash = hash_string(&voter_key);
 
-    let voting =
-        find_voting(&conn, &voting_id).and_then(|voting| check_if_voting_admin(voting, &user))?;
+    let voting = find_voting(&conn, &voting_id)
+        .and_then(|voting| check_if_voting_admin(&conn, voting, &user, AdminScope::Voters))
+        .and_then(check_if_not_archived)?;
+
+    if input.casting_voter == Some(true) && voting.tie_policy != TiePolicy::CastingVoter {
+        return Err(ErrorResponse {
+            reason: format!(
+                "Voting with id: {} does not have the tie policy CASTING_VOTER",
+                voting.id
+            ),
+            status: Status::BadRequest,
+        });
+    }
 
-    insert_voter(&conn, &input.username, &voter_key_hash, &voting.id)?;
+    let voter_id = insert_voter(
+        &conn,
+        &input.username,
+        &voter_key_hash,
+        &voting.id,
+        input.weight.unwrap_or(1),
+        input.email.as_ref(),
+    )
+    .map_err(|err| {
+        let error_msg = format!("Could not insert voter for voting with id: {}", voting.id);
+        println!("{}. err: {:?}", error_msg, err);
+        ErrorResponse {
+            reason: error_msg,
+            status: Status::InternalServerError,
+        }
+    })?;
+    if input.casting_voter == Some(true) {
+        update_voting_casting_voter(&conn, &voting, &voter_id)?;
+    }
 
     Ok(Json(create_voter_dto::CreateVoterResponse {
         voter_key,
+        voter_id,
         voting_id: voting.id,
     }))
 }
 
+/// Creates all voters of the import, or none of them if any row is invalid.
+/// Responds with a CSV sheet of the voter keys.
+#[post(
+    "/votings/<voting_id>/voters/import",
+    format = "json",
+    data = "<input>"
+)]
+pub fn import_voters(
+    conn: DbConn,
+    voting_id: String,
+    input: Json<import_voters_dto::ImportVotersRequest>,
+    user: AuthenticatedUser,
+) -> Result<CsvAttachment, RowErrorsResponse> {
+    import(&conn, &voting_id, &input.voters, &user)
+}
+
+#[post(
+    "/votings/<voting_id>/voters/import",
+    format = "text/csv",
+    data = "<data>"
+)]
+pub fn import_voters_csv(
+    conn: DbConn,
+    voting_id: String,
+    data: Data,
+    user: AuthenticatedUser,
+) -> Result<CsvAttachment, RowErrorsResponse> {
+    // One byte more than the limit tells a body at the limit from a longer one.
+    let mut bytes = Vec::new();
+    data.open()
+        .take(IMPORT_CSV_LIMIT + 1)
+        .read_to_end(&mut bytes)
+        .map_err(|_| ErrorResponse {
+            reason: "Could not read the CSV import".to_string(),
+            status: Status::BadRequest,
+        })?;
+    if bytes.len() as u64 > IMPORT_CSV_LIMIT {
+        return Err(ErrorResponse {
+            reason: format!(
+                "The CSV import can not be larger than {} bytes",
+                IMPORT_CSV_LIMIT
+            ),
+            status: Status::PayloadTooLarge,
+        }
+        .into());
+    }
+    let body = String::from_utf8(bytes).map_err(|_| ErrorResponse {
+        reason: "Could not read the CSV import, it must be UTF-8".to_string(),
+        status: Status::BadRequest,
+    })?;
+
+    import(&conn, &voting_id, &parse_import_csv(&body)?, &user)
+}
+
+fn parse_import_csv(
+    body: &str,
+) -> Result<Vec<import_voters_dto::ImportVoterRequest>, RowErrorsResponse> {
+    let records = csv::parse(body).map_err(|reason| ErrorResponse {
+        reason,
+        status: Status::BadRequest,
+    })?;
+    let (header, rows) = match records.split_first() {
+        Some((header, rows)) => (header, rows),
+        None => {
+            return Err(RowErrorsResponse::from(ErrorResponse {
+                reason: "The CSV import needs a header row".to_string(),
+                status: Status::BadRequest,
+            }))
+        }
+    };
+
+    let mut columns = (None, None, None);
+    for (index, column) in header.iter().enumerate() {
+        match column.trim().to_lowercase().as_str() {
+            "username" => columns.0 = Some(index),
+            "weight" => columns.1 = Some(index),
+            "email" => columns.2 = Some(index),
+            column => {
+                return Err(RowErrorsResponse::from(ErrorResponse {
+                    reason: format!("Unknown column: {} in the CSV import", column),
+                    status: Status::BadRequest,
+                }))
+            }
+        }
+    }
+    let username_column = columns.0.ok_or_else(|| ErrorResponse {
+        reason: "The CSV import needs a username column".to_string(),
+        status: Status::BadRequest,
+    })?;
+
+    let mut voters = Vec::new();
+    let mut row_errors = Vec::new();
+    for (index, record) in rows.iter().enumerate() {
+        if record.len() != header.len() {
+            row_errors.push(RowError {
+                row: index + 1,
+                reason: format!(
+                    "Row has {} fields but the header has {}",
+                    record.len(),
+                    header.len()
+                ),
+            });
+            continue;
+        }
+        let field = |column: Option<usize>| {
+            column
+                .map(|column| record[column].trim().to_string())
+                .filter(|field| !field.is_empty())
+        };
+        let weight = match field(columns.1) {
+            Some(weight) => match weight.parse::<i32>() {
+                Ok(weight) => Some(weight),
+                Err(_) => {
+                    row_errors.push(RowError {
+                        row: index + 1,
+                        reason: format!("Voter weight: {} is not a number", weight),
+                    });
+                    continue;
+                }
+            },
+            None => None,
+        };
+        voters.push(import_voters_dto::ImportVoterRequest {
+            username: record[username_column].trim().to_string(),
+            weight,
+            email: field(columns.2),
+        });
+    }
+
+    match row_errors.is_empty() {
+        true => Ok(voters),
+        false => Err(RowErrorsResponse {
+            reason: "Some rows of the CSV import are invalid".to_string(),
+            status: Status::BadRequest,
+            row_errors,
+        }),
+    }
+}
+
+fn import(
+    conn: &DbConn,
+    voting_id: &String,
+    voters: &[import_voters_dto::ImportVoterRequest],
+    user: &AuthenticatedUser,
+) -> Result<CsvAttachment, RowErrorsResponse> {
+    validate_voting_id(voting_id)?;
+    match voters.len() {
+        1..=10_000 => Ok(()),
+        _ => Err(ErrorResponse {
+            reason: "An import must have between 1 and 10000 voters".to_string(),
+            status: Status::BadRequest,
+        }),
+    }?;
+
+    let voting = find_voting(conn, voting_id)
+        .and_then(|voting| check_if_voting_admin(conn, voting, user, AdminScope::Voters))
+        .and_then(check_if_not_archived)?;
+
+    let mut usernames: HashSet<String> = find_voters(conn, &voting.id)?
+        .into_iter()
+        .map(|voter| voter.username)
+        .collect();
+    let mut row_errors = Vec::new();
+    for (index, voter) in voters.iter().enumerate() {
+        let validation = validate_voter_username(&voter.username)
+            .and_then(|_| validate_voter_weight(voter.weight))
+            .and_then(|_| validate_voter_email(&voter.email))
+            .and_then(|_| match usernames.insert(voter.username.to_string()) {
+                true => Ok(()),
+                false => Err(ErrorResponse {
+                    reason: format!(
+                        "Voter username: {} is already used in this voting",
+                        voter.username
+                    ),
+                    status: Status::BadRequest,
+                }),
+            });
+        if let Err(err) = validation {
+            row_errors.push(RowError {
+                row: index + 1,
+                reason: err.reason,
+            });
+        }
+    }
+    if !row_errors.is_empty() {
+        return Err(RowErrorsResponse {
+            reason: "Some rows of the import are invalid, no voter was created".to_string(),
+            status: Status::BadRequest,
+            row_errors,
+        });
+    }
+
+    let mut sheet = csv::write_record(&["username", "email", "weight", "voterId", "voterKey"]);
+    conn.transaction::<_, Error, _>(|| {
+        for voter in voters {
+            let voter_key = generate_uuid();
+            let weight = voter.weight.unwrap_or(1);
+            let voter_id = insert_voter(
+                conn,
+                &voter.username,
+                &hash_string(&voter_key),
+                &voting.id,
+                weight,
+                voter.email.as_ref(),
+            )?;
+            sheet.push_str(&csv::write_record(&[
+                &voter.username,
+                voter.email.as_deref().unwrap_or(""),
+                &weight.to_string(),
+                &voter_id,
+                &voter_key,
+            ]));
+        }
+        Ok(())
+    })
+    .map_err(|err| {
+        let error_msg = format!("Could not import voters for voting with id: {}", voting.id);
+        println!("{}. err: {:?}", error_msg, err);
+        ErrorResponse {
+            reason: error_msg,
+            status: Status::InternalServerError,
+        }
+    })?;
+
+    Ok(CsvAttachment {
+        filename: format!("voter-keys-{}.csv", voting.id),
+        body: sheet,
+    })
+}
+
+#[post(
+    "/votings/<voting_id>/voters/<voter_id>/proxy",
+    format = "json",
+    data = "<input>"
+)]
+pub fn set_proxy(
+    conn: DbConn,
+    voting_id: String,
+    voter_id: String,
+    input: Json<set_proxy_dto::SetProxyRequest>,
+    user: AuthenticatedUser,
+) -> Result<Json<()>, ErrorResponse> {
+    validate_voting_id(&voting_id)?;
+    validate_voter_id(&voter_id)?;
+    validate_voter_id(&input.proxy_voter_id)?;
+
+    let voting = find_voting(&conn, &voting_id)
+        .and_then(|voting| check_if_voting_admin(&conn, voting, &user, AdminScope::Voters))
+        .and_then(check_if_not_archived)?;
+
+    if voter_id == input.proxy_voter_id {
+        return Err(ErrorResponse {
+            reason: "Voter can not be their own proxy".to_string(),
+            status: Status::BadRequest,
+        });
+    }
+
+    let voter = find_voting_voter(&conn, &voting, &voter_id).and_then(check_if_not_revoked)?;
+    let proxy_voter =
+        find_voting_voter(&conn, &voting, &input.proxy_voter_id).and_then(check_if_not_revoked)?;
+
+    if find_proxy(&conn, &voter.id)?.is_some() {
+        return Err(ErrorResponse {
+            reason: format!(
+                "Voter with id: {} already gave their ballot to a proxy",
+                voter.id
+            ),
+            status: Status::BadRequest,
+        });
+    }
+    // Proxies can not be passed on, so neither end of a new proxy can already
+    // be part of another one in the opposite role.
+    if !find_held_proxies(&conn, &voter.id)?.is_empty() {
+        return Err(ErrorResponse {
+            reason: format!(
+                "Voter with id: {} holds ballots of other voters and can not give their ballot to a proxy",
+                voter.id
+            ),
+            status: Status::BadRequest,
+        });
+    }
+    if find_proxy(&conn, &proxy_voter.id)?.is_some() {
+        return Err(ErrorResponse {
+            reason: format!(
+                "Proxy voter with id: {} gave their own ballot to a proxy",
+                proxy_voter.id
+            ),
+            status: Status::BadRequest,
+        });
+    }
+    if find_held_proxies(&conn, &proxy_voter.id)?.len() as i32 >= voting.max_proxies_per_voter {
+        return Err(ErrorResponse {
+            reason: format!(
+                "Proxy voter with id: {} already holds the maximum of {} ballots of other voters",
+                proxy_voter.id, voting.max_proxies_per_voter
+            ),
+            status: Status::BadRequest,
+        });
+    }
+
+    insert_proxy(&conn, &voting.id, &voter.id, &proxy_voter.id)?;
+    Ok(Json(()))
+}
+
+#[delete("/votings/<voting_id>/voters/<voter_id>/proxy", format = "json")]
+pub fn delete_proxy(
+    conn: DbConn,
+    voting_id: String,
+    voter_id: String,
+    user: AuthenticatedUser,
+) -> Result<Json<()>, ErrorResponse> {
+    validate_voting_id(&voting_id)?;
+    validate_voter_id(&voter_id)?;
+
+    let voting = find_voting(&conn, &voting_id)
+        .and_then(|voting| check_if_voting_admin(&conn, voting, &user, AdminScope::Voters))
+        .and_then(check_if_not_archived)?;
+    let voter = find_voting_voter(&conn, &voting, &voter_id)?;
+
+    match find_proxy(&conn, &voter.id)? {
+        Some(proxy) => delete::delete_proxy(&conn, &proxy)?,
+        None => {
+            return Err(ErrorResponse {
+                reason: format!("Voter with id: {} has not given a proxy", voter.id),
+                status: Status::NotFound,
+            })
+        }
+    };
+    Ok(Json(()))
+}
+
 #[get("/votings/<voting_id>/voters/info", format = "json")]
 pub fn get_voter_info(
     conn: DbConn,
@@ -45,10 +431,241 @@ pub fn get_voter_info(
     let voting =
         find_voting(&conn, &voting_id).and_then(|voting| check_if_voter(&conn, voting, &user))?;
 
-    let voter = find_voter(&conn, &user)?;
+    let voter = find_voter(&conn, &user).and_then(|voter| check_if_voting_voter(&voting, voter))?;
 
     Ok(Json(get_voter_info_dto::GetVoterInfoResponse {
         voting_name: voting.name,
+        voter_id: voter.id,
         username: voter.username,
     }))
 }
+
+#[get("/votings/<voting_id>/voters", format = "json")]
+pub fn get_voters(
+    conn: DbConn,
+    voting_id: String,
+    user: AuthenticatedUser,
+) -> Result<Json<Vec<get_voters_dto::GetVoterResponse>>, ErrorResponse> {
+    validate_voting_id(&voting_id)?;
+
+    let voting = find_voting(&conn, &voting_id)
+        .and_then(|voting| check_if_voting_admin(&conn, voting, &user, AdminScope::Voters))?;
+
+    Ok(Json(
+        find_voters(&conn, &voting.id)?
+            .into_iter()
+            .map(|voter| get_voters_dto::GetVoterResponse {
+                voter_id: voter.id,
+                username: voter.username,
+                weight: voter.weight,
+                email: voter.email,
+                revoked_at: voter.revoked_at,
+            })
+            .collect(),
+    ))
+}
+
+#[put(
+    "/votings/<voting_id>/voters/<voter_id>",
+    format = "json",
+    data = "<input>"
+)]
+pub fn update_voter(
+    conn: DbConn,
+    voting_id: String,
+    voter_id: String,
+    input: Json<update_voter_dto::UpdateVoterRequest>,
+    user: AuthenticatedUser,
+) -> Result<Json<()>, ErrorResponse> {
+    validate_voting_id(&voting_id)?;
+    validate_voter_id(&voter_id)?;
+    validate_voter_username(&input.username)?;
+
+    let voting = find_voting(&conn, &voting_id)
+        .and_then(|voting| check_if_voting_admin(&conn, voting, &user, AdminScope::Voters))
+        .and_then(check_if_not_archived)?;
+    let voter = find_voting_voter(&conn, &voting, &voter_id)?;
+
+    if find_voters(&conn, &voting.id)?
+        .iter()
+        .any(|other| other.id != voter.id && other.username == input.username)
+    {
+        return Err(ErrorResponse {
+            reason: format!(
+                "Voter username: {} is already used in this voting",
+                input.username
+            ),
+            status: Status::BadRequest,
+        });
+    }
+
+    update_voter_username(&conn, &voter, &input.username)?;
+    Ok(Json(()))
+}
+
+/// Rejects all future votes of the voter and of their proxy for them. The
+/// ballots they already cast are kept.
+#[post("/votings/<voting_id>/voters/<voter_id>/revoke", format = "json")]
+pub fn revoke_voter(
+    conn: DbConn,
+    voting_id: String,
+    voter_id: String,
+    user: AuthenticatedUser,
+) -> Result<Json<()>, ErrorResponse> {
+    validate_voting_id(&voting_id)?;
+    validate_voter_id(&voter_id)?;
+
+    let voting = find_voting(&conn, &voting_id)
+        .and_then(|voting| check_if_voting_admin(&conn, voting, &user, AdminScope::Voters))
+        .and_then(check_if_not_archived)?;
+    let voter = find_voting_voter(&conn, &voting, &voter_id)?;
+
+    if voter.revoked_at.is_some() {
+        return Err(ErrorResponse {
+            reason: format!("Voter with id: {} is already revoked", voter.id),
+            status: Status::BadRequest,
+        });
+    }
+
+    update_voter_revoked(&conn, &voter, Utc::now().naive_utc())?;
+    Ok(Json(()))
+}
+
+/// Replaces the key of the voter, the old key stops working immediately.
+#[post("/votings/<voting_id>/voters/<voter_id>/key", format = "json")]
+pub fn regenerate_voter_key(
+    conn: DbConn,
+    voting_id: String,
+    voter_id: String,
+    user: AuthenticatedUser,
+) -> Result<Json<regenerate_voter_key_dto::RegenerateVoterKeyResponse>, ErrorResponse> {
+    validate_voting_id(&voting_id)?;
+    validate_voter_id(&voter_id)?;
+
+    let voting = find_voting(&conn, &voting_id)
+        .and_then(|voting| check_if_voting_admin(&conn, voting, &user, AdminScope::Voters))
+        .and_then(check_if_not_archived)?;
+    let voter = find_voting_voter(&conn, &voting, &voter_id)?;
+
+    let voter_key = generate_uuid();
+    update_voter_key_hash(&conn, &voter, &hash_string(&voter_key))?;
+
+    Ok(Json(regenerate_voter_key_dto::RegenerateVoterKeyResponse {
+        voter_key,
+        voter_id: voter.id,
+    }))
+}
+
+/// Every voter with their participation in each poll. With `pending` only the
+/// voters who did not yet vote on an open poll they may vote on are listed.
+#[get("/votings/<voting_id>/roster?<pending>", format = "json")]
+pub fn get_roster(
+    conn: DbConn,
+    voting_id: String,
+    pending: Option<bool>,
+    user: AuthenticatedUser,
+) -> Result<Json<Vec<get_roster_dto::GetRosterVoterResponse>>, ErrorResponse> {
+    validate_voting_id(&voting_id)?;
+
+    let voting = find_voting(&conn, &voting_id)
+        .and_then(|voting| check_if_voting_admin(&conn, voting, &user, AdminScope::Results))?;
+    apply_schedule(&conn, &voting)?;
+
+    let polls = find_polls(&conn, &voting.id)?;
+    let participations = find_voting_participations(&conn, &voting.id)?;
+    // Secret ballots are stored without the voter, so there are no choices
+    // to show.
+    let mut choices_of_ballot: HashMap<(&String, String), Vec<VoteChoice>> = HashMap::new();
+    if !voting.secret {
+        for poll in &polls {
+            for (choice, named_vote) in find_poll_vote_choices(&conn, &poll.id)? {
+                if let Some(named_vote) = named_vote {
+                    choices_of_ballot
+                        .entry((&poll.id, named_vote.voter_fk))
+                        .or_default()
+                        .push(choice);
+                }
+            }
+        }
+    }
+
+    // Polls for present voters only are pending just for the voters who may
+    // vote on them.
+    let mut eligible_voters_of_poll: HashMap<&String, Vec<Voter>> = HashMap::new();
+    for poll in &polls {
+        if let (PollState::Open, true, Some(opened_at)) =
+            (poll.state, poll.present_voters_only, poll.opened_at)
+        {
+            eligible_voters_of_poll.insert(
+                &poll.id,
+                find_eligible_voters(&conn, &voting.id, opened_at)?,
+            );
+        }
+    }
+
+    let mut roster = Vec::new();
+    for voter in find_voters(&conn, &voting.id)? {
+        let participation_in = |poll: &Poll| {
+            participations.iter().find(|participation| {
+                participation.poll_fk == poll.id && participation.voter_fk == voter.id
+            })
+        };
+        let is_eligible_for = |poll: &Poll| match eligible_voters_of_poll.get(&poll.id) {
+            Some(eligible_voters) => eligible_voters
+                .iter()
+                .any(|eligible_voter| eligible_voter.id == voter.id),
+            None => true,
+        };
+        let is_pending = voter.revoked_at.is_none()
+            && polls.iter().any(|poll| {
+                poll.state == PollState::Open
+                    && participation_in(poll).is_none()
+                    && is_eligible_for(poll)
+            });
+        if pending == Some(true) && !is_pending {
+            continue;
+        }
+
+        let polls_response = polls
+            .iter()
+            .map(|poll| {
+                let participation = participation_in(poll);
+                let choices = match (voting.secret, participation) {
+                    (false, Some(_)) => Some(
+                        choices_of_ballot
+                            .get(&(&poll.id, voter.id.to_string()))
+                            .map(|choices| {
+                                choices
+                                    .iter()
+                                    .map(|choice| get_roster_dto::GetRosterChoiceResponse {
+                                        option_id: choice.poll_option_fk.to_string(),
+                                        rank: choice.rank,
+                                        score: choice.score,
+                                    })
+                                    .collect()
+                            })
+                            .unwrap_or_default(),
+                    ),
+                    _ => None,
+                };
+                get_roster_dto::GetRosterPollResponse {
+                    poll_id: poll.id.to_string(),
+                    voted: participation.is_some(),
+                    proxy_voter_id: participation
+                        .and_then(|participation| participation.proxy_voter_fk.clone()),
+                    choices,
+                }
+            })
+            .collect();
+
+        roster.push(get_roster_dto::GetRosterVoterResponse {
+            voter_id: voter.id,
+            username: voter.username,
+            weight: voter.weight,
+            revoked_at: voter.revoked_at,
+            polls: polls_response,
+        });
+    }
+
+    Ok(Json(roster))
+}
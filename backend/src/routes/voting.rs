@@ -1,47 +1,148 @@
 use crate::pool::DbConn;
 
 use crate::actions::check::*;
+use crate::actions::delete;
 use crate::actions::find::*;
 use crate::actions::insert::*;
+use crate::actions::update::*;
 
-use crate::dtos::{create_voting_dto, get_voting_dto};
-use crate::models::PollResult;
+use crate::dtos::{clone_voting_dto, create_voting_dto, get_voting_dto, set_tie_decision_dto};
+use crate::models::{
+    NamedVote, NewPoll, NewVoting, Poll, PollOptionResult, PollResult, VoteChoice, Voting,
+};
+use crate::routes::template::find_template_definition;
+use crate::tally::delegation::{self, DelegationResult};
+use crate::tally::instant_runoff::{self, InstantRunoffResult};
+use crate::tally::majority;
+use crate::tally::schulze::{self, SchulzeResult};
+use crate::tally::Ballot;
+use crate::types::{
+    AdminScope, MajorityRule, PollKey, PollState, PollType, PresetAnswer, TiePolicy, VoteCounting,
+    YES_NO_PRESET,
+};
 use crate::utils::{generate_uuid, hash_string, AuthenticatedUser, ErrorResponse};
-use crate::validators::{validate_create_voting_request, validate_voting_id};
+use crate::validators::{validate_create_voting_request, validate_poll_id, validate_voting_id};
 
+use chrono::{NaiveDateTime, Utc};
 use diesel::prelude::*;
 use diesel::result::Error;
 use rocket::http::Status;
 use rocket_contrib::json::Json;
+use std::collections::{HashMap, HashSet};
+
+/// Label of the admin key a voting is created with.
+const CHAIR_ADMIN_KEY_LABEL: &str = "Chair";
 
 #[post("/votings", format = "json", data = "<input>")]
 pub fn create_voting(
     conn: DbConn,
     input: Json<create_voting_dto::CreateVotingRequest>,
+    user: Option<AuthenticatedUser>,
 ) -> Result<Json<create_voting_dto::CreateVotingResponse>, ErrorResponse> {
+    let input = input.into_inner();
+    // Starting from a template needs the admin key of the template.
+    let input = match (input.template.clone(), user) {
+        (Some(template_name), Some(user)) => {
+            input.with_template(find_template_definition(&conn, &template_name, &user)?)
+        }
+        (Some(template_name), None) => {
+            return Err(ErrorResponse {
+                reason: format!(
+                    "Admin key is required for template with name: {}",
+                    template_name
+                ),
+                status: Status::Unauthorized,
+            })
+        }
+        (None, _) => input,
+    };
     validate_create_voting_request(&input)?;
 
     let admin_key = generate_uuid();
     let admin_key_hash = hash_string(&admin_key);
+    let recovery_secret = match input.recoverable {
+        Some(true) => Some(generate_uuid()),
+        _ => None,
+    };
+    let recovery_secret_hash = recovery_secret.as_ref().map(hash_string);
 
     let voting_id = conn
         .transaction::<String, Error, _>(|| {
-            let voting_id = insert_voting(&conn, &input.name, &admin_key_hash)?;
+            insert_voting_with_polls(
+                &conn,
+                &admin_key_hash,
+                recovery_secret_hash.as_ref(),
+                &input,
+            )
+        })
+        .map_err(|err| {
+            let error_msg = "Could not insert voting to database".to_string();
+            println!("{}. err: {:?}", error_msg, err);
+            ErrorResponse {
+                reason: error_msg,
+                status: Status::InternalServerError,
+            }
+        })?;
 
-            for (i, poll) in (&input.polls).iter().enumerate() {
-                insert_poll(
+    Ok(Json(create_voting_dto::CreateVotingResponse {
+        voting_id,
+        admin_key,
+        recovery_secret,
+    }))
+}
+
+/// Copies the settings and polls of the voting into a new voting with a new
+/// admin key. Votes, schedules, the state of the polls and the recovery secret
+/// are not copied.
+#[post("/votings/<voting_id>/clone", format = "json", data = "<input>")]
+pub fn clone_voting(
+    conn: DbConn,
+    voting_id: String,
+    input: Json<clone_voting_dto::CloneVotingRequest>,
+    user: AuthenticatedUser,
+) -> Result<Json<clone_voting_dto::CloneVotingResponse>, ErrorResponse> {
+    validate_voting_id(&voting_id)?;
+
+    let voting = find_voting(&conn, &voting_id)
+        .and_then(|voting| check_if_voting_admin(&conn, voting, &user, AdminScope::Full))?;
+    let mut definition = get_voting_definition(&conn, &voting)?;
+    if let Some(name) = &input.name {
+        definition.name = name.to_string();
+    }
+    validate_create_voting_request(&definition)?;
+    // Revoked voters stay behind instead of coming back with a new key.
+    let voters = match input.copy_voters {
+        Some(true) => find_voters(&conn, &voting.id)?
+            .into_iter()
+            .filter(|voter| voter.revoked_at.is_none())
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    let admin_key = generate_uuid();
+    let admin_key_hash = hash_string(&admin_key);
+
+    let (cloned_voting_id, voters_response) = conn
+        .transaction::<_, Error, _>(|| {
+            let cloned_voting_id =
+                insert_voting_with_polls(&conn, &admin_key_hash, None, &definition)?;
+            let mut voters_response = Vec::new();
+            for voter in &voters {
+                let voter_key = generate_uuid();
+                let voter_id = insert_voter(
                     &conn,
-                    &poll.name,
-                    (i * 10) as i32,
-                    &poll.description,
-                    &voting_id,
+                    &voter.username,
+                    &hash_string(&voter_key),
+                    &cloned_voting_id,
+                    voter.weight,
+                    voter.email.as_ref(),
                 )?;
+                voters_response.push((voter.id.to_string(), voter_id, voter_key));
             }
-
-            Ok(voting_id)
+            Ok((cloned_voting_id, voters_response))
         })
         .map_err(|err| {
-            let error_msg = "Could not insert voting to database".to_string();
+            let error_msg = format!("Could not clone voting with id: {}", voting.id);
             println!("{}. err: {:?}", error_msg, err);
             ErrorResponse {
                 reason: error_msg,
@@ -49,12 +150,169 @@ pub fn create_voting(
             }
         })?;
 
-    Ok(Json(create_voting_dto::CreateVotingResponse {
-        voting_id,
+    if let Some((_, casting_voter_id, _)) = voters_response
+        .iter()
+        .find(|(voter_id, _, _)| Some(voter_id) == voting.casting_voter_fk.as_ref())
+    {
+        let cloned_voting = find_voting(&conn, &cloned_voting_id)?;
+        update_voting_casting_voter(&conn, &cloned_voting, casting_voter_id)?;
+    }
+
+    Ok(Json(clone_voting_dto::CloneVotingResponse {
+        voting_id: cloned_voting_id,
         admin_key,
+        voters: voters
+            .into_iter()
+            .zip(voters_response)
+            .map(
+                |(voter, (_, voter_id, voter_key))| clone_voting_dto::CloneVotingVoterResponse {
+                    voter_id,
+                    username: voter.username,
+                    voter_key,
+                },
+            )
+            .collect(),
     }))
 }
 
+/// The voting as the request that creates it again, without the schedules of
+/// its polls.
+pub fn get_voting_definition(
+    conn: &DbConn,
+    voting: &Voting,
+) -> Result<create_voting_dto::CreateVotingRequest, ErrorResponse> {
+    let mut polls = Vec::new();
+    for poll in find_polls(conn, &voting.id)? {
+        let options = match poll.poll_type {
+            PollType::YesNo => None,
+            _ => Some(
+                find_poll_options(conn, &poll.id)?
+                    .into_iter()
+                    .map(|option| option.name)
+                    .collect(),
+            ),
+        };
+        polls.push(create_voting_dto::CreateVotingPollRequest {
+            name: poll.name,
+            description: poll.description,
+            poll_type: Some(poll.poll_type),
+            options,
+            min_approvals: poll.min_approvals,
+            max_approvals: poll.max_approvals,
+            max_score: poll.max_score,
+            majority_rule: Some(poll.majority_rule),
+            quorum_percent: poll.quorum_percent,
+            vote_counting: Some(poll.vote_counting),
+            topic: poll.topic,
+            opens_at: None,
+            closes_at: None,
+            time_limit_seconds: poll.time_limit_seconds,
+            present_voters_only: Some(poll.present_voters_only),
+        });
+    }
+
+    Ok(create_voting_dto::CreateVotingRequest {
+        name: voting.name.to_string(),
+        polls,
+        template: None,
+        tie_policy: Some(voting.tie_policy),
+        max_proxies_per_voter: Some(voting.max_proxies_per_voter),
+        secret: Some(voting.secret),
+        allow_vote_change: Some(voting.allow_vote_change),
+        max_vote_changes: voting.max_vote_changes,
+        auto_advance: Some(voting.auto_advance),
+        recoverable: None,
+    })
+}
+
+/// Inserts the voting with all of its polls and a `FULL` admin key.
+pub fn insert_voting_with_polls(
+    conn: &DbConn,
+    admin_key_hash: &String,
+    recovery_secret_hash: Option<&String>,
+    input: &create_voting_dto::CreateVotingRequest,
+) -> QueryResult<String> {
+    let voting_id = insert_voting(
+        conn,
+        &NewVoting {
+            name: &input.name,
+            tie_policy: input.tie_policy.unwrap_or(TiePolicy::Draw),
+            max_proxies_per_voter: input.max_proxies_per_voter.unwrap_or(1),
+            secret: input.secret.unwrap_or(false),
+            allow_vote_change: input.allow_vote_change.unwrap_or(false),
+            max_vote_changes: input.max_vote_changes,
+            auto_advance: input.auto_advance.unwrap_or(false),
+            recovery_secret_hash,
+        },
+    )?;
+    insert_admin_key(
+        conn,
+        &voting_id,
+        admin_key_hash,
+        &CHAIR_ADMIN_KEY_LABEL.to_string(),
+        AdminScope::Full,
+    )?;
+
+    for (i, poll) in input.polls.iter().enumerate() {
+        insert_poll_with_options(conn, &voting_id, (i * 10) as i32, poll)?;
+    }
+
+    Ok(voting_id)
+}
+
+/// Inserts the poll with its options, or the `YES_NO` preset when it has
+/// none.
+pub fn insert_poll_with_options(
+    conn: &DbConn,
+    voting_id: &String,
+    sequenz_number: i32,
+    poll: &create_voting_dto::CreateVotingPollRequest,
+) -> QueryResult<String> {
+    let approval_bounds = poll.get_approval_bounds();
+    let poll_id = insert_poll(
+        conn,
+        &NewPoll {
+            sequenz_number,
+            voting_fk: voting_id,
+            name: &poll.name,
+            description: &poll.description,
+            poll_type: poll.get_poll_type(),
+            min_approvals: approval_bounds.map(|(min_approvals, _)| min_approvals),
+            max_approvals: approval_bounds.map(|(_, max_approvals)| max_approvals),
+            max_score: poll.get_max_score(),
+            majority_rule: poll.majority_rule.unwrap_or(MajorityRule::Simple),
+            quorum_percent: poll.quorum_percent,
+            vote_counting: poll.vote_counting.unwrap_or(VoteCounting::Heads),
+            topic: poll.topic.as_ref(),
+            opens_at: poll.opens_at,
+            closes_at: poll.closes_at,
+            time_limit_seconds: poll.time_limit_seconds,
+            present_voters_only: poll.present_voters_only.unwrap_or(false),
+        },
+    )?;
+
+    match &poll.options {
+        Some(options) => {
+            for (j, option) in options.iter().enumerate() {
+                insert_poll_option(conn, option, (j * 10) as i32, &poll_id, None)?;
+            }
+        }
+        None => {
+            for (j, preset_answer) in YES_NO_PRESET.iter().enumerate() {
+                insert_poll_option(
+                    conn,
+                    preset_answer.option_name(),
+                    (j * 10) as i32,
+                    &poll_id,
+                    Some(*preset_answer),
+                )?;
+            }
+        }
+    }
+
+    Ok(poll_id)
+}
+
 #[get("/votings/<voting_id>", format = "json")]
 pub fn get_voting(
     conn: DbConn,
@@ -63,56 +321,819 @@ pub fn get_voting(
 ) -> Result<Json<get_voting_dto::GetVotingResponse>, ErrorResponse> {
     validate_voting_id(&voting_id)?;
 
-    find_voting(&conn, &voting_id)
-        .and_then(|voting| check_if_voting_admin(voting, &user))
-        .and_then(|voting| {
-            Ok((
-                get_voting_polls_response(&conn, &voting.id)?,
-                find_amount_of_voters(&conn, &voting.id)?,
-                voting,
-            ))
-        })
-        .map(|(polls_response, voter_count, voting)| {
-            Json(get_voting_dto::GetVotingResponse {
-                voting_id: voting.id,
-                name: voting.name,
-                active_poll_index: voting.active_poll_index,
-                polls: polls_response,
-                voter_count,
-            })
+    let voting = find_voting(&conn, &voting_id)
+        .and_then(|voting| check_if_voting_admin(&conn, voting, &user, AdminScope::Results))?;
+    let voter_count = find_amount_of_voters(&conn, &voting.id)?;
+    let voter_weight = find_total_voter_weight(&conn, &voting.id)?;
+    let polls_response = get_voting_polls_response(&conn, &voting, voter_count, voter_weight)?;
+    let open_poll_indexes = polls_response
+        .iter()
+        .enumerate()
+        .filter(|(_, poll)| poll.state == PollState::Open)
+        .map(|(poll_index, _)| poll_index as i32)
+        .collect::<Vec<i32>>();
+
+    let voters = find_voters(&conn, &voting.id)?;
+    let username_of = |voter_id: &String| {
+        voters
+            .iter()
+            .find(|voter| &voter.id == voter_id)
+            .map(|voter| voter.username.to_string())
+            .unwrap_or_default()
+    };
+    let proxies = find_proxies(&conn, &voting.id)?
+        .into_iter()
+        .map(|proxy| get_voting_dto::GetVotingProxyResponse {
+            username: username_of(&proxy.voter_fk),
+            proxy_username: username_of(&proxy.proxy_voter_fk),
+            voter_id: proxy.voter_fk,
+            proxy_voter_id: proxy.proxy_voter_fk,
         })
+        .collect();
+
+    Ok(Json(get_voting_dto::GetVotingResponse {
+        casting_voter_username: voting.casting_voter_fk.as_ref().map(username_of),
+        voting_id: voting.id,
+        name: voting.name,
+        active_poll_index: open_poll_indexes.first().copied(),
+        open_poll_indexes,
+        polls: polls_response,
+        voter_count,
+        voter_weight,
+        tie_policy: voting.tie_policy,
+        max_proxies_per_voter: voting.max_proxies_per_voter,
+        proxies,
+        secret: voting.secret,
+        allow_vote_change: voting.allow_vote_change,
+        max_vote_changes: voting.max_vote_changes,
+        agenda_poll_index: voting.agenda_poll_index,
+        archived_at: voting.archived_at,
+        auto_advance: voting.auto_advance,
+        recoverable: voting.recovery_secret_hash.is_some(),
+    }))
 }
 
-fn get_voting_polls_response(
+#[delete("/votings/<voting_id>", format = "json")]
+pub fn delete_voting(
+    conn: DbConn,
+    voting_id: String,
+    user: AuthenticatedUser,
+) -> Result<Json<()>, ErrorResponse> {
+    validate_voting_id(&voting_id)?;
+
+    let voting = find_voting(&conn, &voting_id)
+        .and_then(|voting| check_if_voting_admin(&conn, voting, &user, AdminScope::Full))?;
+
+    delete::delete_voting(&conn, &voting).map(Json)
+}
+
+/// Closes the open polls and freezes the voting as read-only. Archived
+/// votings are purged once they are older than the retention period.
+#[post("/votings/<voting_id>/archive", format = "json")]
+pub fn archive_voting(
+    conn: DbConn,
+    voting_id: String,
+    user: AuthenticatedUser,
+) -> Result<Json<()>, ErrorResponse> {
+    validate_voting_id(&voting_id)?;
+
+    let voting = find_voting(&conn, &voting_id)
+        .and_then(|voting| check_if_voting_admin(&conn, voting, &user, AdminScope::Full))
+        .and_then(check_if_not_archived)?;
+
+    let now = Utc::now().naive_utc();
+    for poll in find_polls(&conn, &voting.id)? {
+        if poll.state == PollState::Open {
+            finalize_poll(&conn, &voting, &poll, now)?;
+        }
+    }
+    update_voting_archived(&conn, &voting, now).map(Json)
+}
+
+#[post(
+    "/votings/<voting_id>/polls/<poll_index>/tie-decision",
+    format = "json",
+    data = "<input>"
+)]
+pub fn set_tie_decision(
+    conn: DbConn,
+    voting_id: String,
+    poll_index: i32,
+    input: Json<set_tie_decision_dto::SetTieDecisionRequest>,
+    user: AuthenticatedUser,
+) -> Result<Json<()>, ErrorResponse> {
+    decide_tie(
+        &conn,
+        &voting_id,
+        &PollKey::Index(poll_index),
+        &input,
+        &user,
+    )
+}
+
+#[post(
+    "/votings/<voting_id>/polls/<poll_id>/tie-decision",
+    format = "json",
+    data = "<input>",
+    rank = 2
+)]
+pub fn set_tie_decision_by_poll_id(
+    conn: DbConn,
+    voting_id: String,
+    poll_id: String,
+    input: Json<set_tie_decision_dto::SetTieDecisionRequest>,
+    user: AuthenticatedUser,
+) -> Result<Json<()>, ErrorResponse> {
+    validate_poll_id(&poll_id)?;
+    decide_tie(&conn, &voting_id, &PollKey::Id(poll_id), &input, &user)
+}
+
+fn decide_tie(
     conn: &DbConn,
     voting_id: &String,
+    poll_key: &PollKey,
+    input: &set_tie_decision_dto::SetTieDecisionRequest,
+    user: &AuthenticatedUser,
+) -> Result<Json<()>, ErrorResponse> {
+    validate_voting_id(voting_id)?;
+
+    let voting = find_voting(conn, voting_id)
+        .and_then(|voting| check_if_voting_admin(conn, voting, user, AdminScope::Agenda))
+        .and_then(check_if_not_archived)?;
+    if voting.tie_policy != TiePolicy::ChairDecides {
+        return Err(ErrorResponse {
+            reason: format!(
+                "Voting with id: {} does not have the tie policy CHAIR_DECIDES",
+                voting.id
+            ),
+            status: Status::BadRequest,
+        });
+    }
+
+    let poll = find_poll_by_key(conn, &voting, poll_key)?;
+    let voter_count = find_amount_of_voters(conn, &voting.id)?;
+    let voter_weight = find_total_voter_weight(conn, &voting.id)?;
+    let tied_option_ids = get_voting_polls_response(conn, &voting, voter_count, voter_weight)?
+        .into_iter()
+        .find(|poll_response| poll_response.poll_id == poll.id)
+        .map(|poll_response| poll_response.tied_option_ids)
+        .unwrap_or_default();
+
+    if tied_option_ids.is_empty() {
+        return Err(ErrorResponse {
+            reason: format!("Poll with {} is not tied", poll_key),
+            status: Status::BadRequest,
+        });
+    }
+    if !tied_option_ids.contains(&input.option_id) {
+        return Err(ErrorResponse {
+            reason: format!(
+                "Option with id: {} is not tied for the lead of the poll",
+                input.option_id
+            ),
+            status: Status::BadRequest,
+        });
+    }
+
+    update_poll_chair_decision(conn, &poll, &input.option_id)?;
+    if poll.state == PollState::Closed {
+        record_final_result(conn, &voting, &poll)?;
+    }
+    Ok(Json(()))
+}
+
+/// Closes the poll and records its result at this moment as final.
+pub fn finalize_poll(
+    conn: &DbConn,
+    voting: &Voting,
+    poll: &Poll,
+    closed_at: NaiveDateTime,
+) -> Result<(), ErrorResponse> {
+    update_poll_state(conn, poll, PollState::Closed, closed_at)?;
+    record_final_result(conn, voting, poll)
+}
+
+fn record_final_result(conn: &DbConn, voting: &Voting, poll: &Poll) -> Result<(), ErrorResponse> {
+    let voter_count = find_amount_of_voters(conn, &voting.id)?;
+    let voter_weight = find_total_voter_weight(conn, &voting.id)?;
+    match get_voting_polls_response(conn, voting, voter_count, voter_weight)?
+        .into_iter()
+        .find(|poll_response| poll_response.poll_id == poll.id)
+    {
+        Some(poll_response) => update_poll_final_result(
+            conn,
+            poll,
+            &poll_response.status,
+            &poll_response.winner_option_id,
+        ),
+        None => Err(ErrorResponse {
+            reason: format!("Could not find the result of poll with id: {}", poll.id),
+            status: Status::InternalServerError,
+        }),
+    }
+}
+
+fn get_voting_polls_response(
+    conn: &DbConn,
+    voting: &Voting,
+    voter_count: i32,
+    voter_weight: i64,
 ) -> Result<Vec<get_voting_dto::GetVotingPollsResponse>, ErrorResponse> {
-    find_poll_results(&conn, &voting_id).map(|loaded_polls| {
-        loaded_polls
+    let mut loaded_options = find_poll_option_results(conn, &voting.id)?;
+    let loaded_polls = find_poll_results(conn, &voting.id)?;
+    let now = Utc::now().naive_utc();
+
+    let mut polls_response = Vec::new();
+    for mut poll in loaded_polls {
+        let (mut poll_options, other_options): (Vec<PollOptionResult>, Vec<PollOptionResult>) =
+            loaded_options
+                .into_iter()
+                .partition(|option| option.poll_fk == poll.id);
+        loaded_options = other_options;
+        let mut ballots = match poll.poll_type.is_ranked() {
+            true => get_ballots_from_choices(
+                find_poll_vote_choices(conn, &poll.id)?,
+                poll.vote_counting,
+            ),
+            false => Vec::new(),
+        };
+        let direct_votes = poll.votes_total;
+        let delegation_result =
+            apply_delegations(conn, voting, &mut poll, &mut poll_options, &mut ballots)?;
+
+        let options = poll_options.iter().collect::<Vec<&PollOptionResult>>();
+        let option_ids = options
+            .iter()
+            .map(|option| option.id.to_string())
+            .collect::<Vec<String>>();
+        let instant_runoff = match poll.poll_type {
+            PollType::InstantRunoff => Some(instant_runoff::tally(&option_ids, &ballots)),
+            _ => None,
+        };
+        let schulze = match poll.poll_type {
+            PollType::Schulze => Some(schulze::tally(&option_ids, &ballots)),
+            _ => None,
+        };
+        // Polls that did not open yet count the voters eligible right now.
+        let (voter_count, voter_weight) = match poll.present_voters_only {
+            true => {
+                let eligible_voters =
+                    find_eligible_voters(conn, &voting.id, poll.opened_at.unwrap_or(now))?;
+                (
+                    eligible_voters.len() as i32,
+                    eligible_voters
+                        .iter()
+                        .map(|voter| voter.weight as i64)
+                        .sum(),
+                )
+            }
+            false => (voter_count, voter_weight),
+        };
+        let electorate = match poll.vote_counting {
+            VoteCounting::Heads => voter_count as i64,
+            VoteCounting::Weights => voter_weight,
+        };
+        let (status, winner_option_id) = get_status_from_poll(
+            &poll,
+            &options,
+            electorate,
+            instant_runoff.as_ref(),
+            schulze.as_ref(),
+        );
+        let tied_option_ids = match status.as_str() {
+            "DRAW" => {
+                find_tied_option_ids(&poll, &options, instant_runoff.as_ref(), schulze.as_ref())
+            }
+            _ => Vec::new(),
+        };
+        let (status, winner_option_id, tie_resolution) =
+            match resolve_tie(conn, voting, &poll, &options, &tied_option_ids)? {
+                Some((status, winner_option_id, tie_resolution)) => {
+                    (status, winner_option_id, Some(tie_resolution))
+                }
+                None => (status, winner_option_id, None),
+            };
+
+        polls_response.push(get_voting_dto::GetVotingPollsResponse {
+            status,
+            winner_option_id,
+            tied_option_ids,
+            tie_resolution,
+            options: options
+                .iter()
+                .map(|option| get_voting_dto::GetVotingPollOptionsResponse {
+                    option_id: option.id.to_string(),
+                    name: option.name.to_string(),
+                    votes: option.votes,
+                    weighted_votes: option.weighted_votes,
+                    score_total: match poll.poll_type {
+                        PollType::Score => Some(option.score_total),
+                        _ => None,
+                    },
+                    weighted_score_total: match poll.poll_type {
+                        PollType::Score => Some(option.weighted_score_total),
+                        _ => None,
+                    },
+                    score_average: match (poll.poll_type, count_option_votes(&poll, option)) {
+                        (PollType::Score, 0) => Some(0.0),
+                        (PollType::Score, votes) => {
+                            Some(count_option_score(&poll, option) as f64 / votes as f64)
+                        }
+                        _ => None,
+                    },
+                })
+                .collect(),
+            instant_runoff: instant_runoff.map(get_instant_runoff_response),
+            schulze: schulze.map(get_schulze_response),
+            poll_id: poll.id,
+            name: poll.name,
+            description: poll.description,
+            poll_type: poll.poll_type,
+            majority_rule: poll.majority_rule,
+            quorum_percent: poll.quorum_percent,
+            vote_counting: poll.vote_counting,
+            topic: poll.topic,
+            is_final: poll.state == PollState::Closed,
+            state: poll.state,
+            opened_at: poll.opened_at,
+            closed_at: poll.closed_at,
+            opens_at: poll.opens_at,
+            closes_at: poll.closes_at,
+            final_status: poll.final_status,
+            final_winner_option_id: poll.final_winner_option_fk,
+            time_limit_seconds: poll.time_limit_seconds,
+            present_voters_only: poll.present_voters_only,
+            voter_count,
+            voter_weight,
+            votes_accept: poll.votes_accept,
+            votes_decline: poll.votes_decline,
+            votes_abstain: poll.votes_abstain,
+            votes_total: poll.votes_total,
+            weighted_votes_accept: poll.weighted_votes_accept,
+            weighted_votes_decline: poll.weighted_votes_decline,
+            weighted_votes_abstain: poll.weighted_votes_abstain,
+            weighted_votes_total: poll.weighted_votes_total,
+            proxy_votes: poll.proxy_votes,
+            direct_votes,
+            delegated_votes: delegation_result.resolved.len() as i64,
+            delegation_cycles: delegation_result.cycles,
+        });
+    }
+
+    Ok(polls_response)
+}
+
+/// Groups the choices, which are ordered by vote and rank, into one ranked
+/// ballot per vote.
+fn get_ballots_from_choices(
+    choices: Vec<(VoteChoice, Option<NamedVote>)>,
+    vote_counting: VoteCounting,
+) -> Vec<Ballot> {
+    let mut ballots: Vec<Ballot> = Vec::new();
+    let mut current_vote_id: Option<String> = None;
+    for (choice, named_vote) in choices {
+        if current_vote_id.as_ref() != Some(&choice.vote_fk) {
+            // Secret ballots have no weight, polls of secret votings count heads.
+            ballots.push(Ballot {
+                choices: Vec::new(),
+                weight: match (vote_counting, named_vote) {
+                    (VoteCounting::Weights, Some(named_vote)) => named_vote.weight as i64,
+                    _ => 1,
+                },
+            });
+            current_vote_id = Some(choice.vote_fk.to_string());
+        }
+        if let Some(ballot) = ballots.last_mut() {
+            ballot.choices.push(choice.poll_option_fk);
+        }
+    }
+    ballots
+}
+
+/// Counts the ballots of delegates for every voter who delegated on the topic
+/// of the poll and did not vote themselves.
+fn apply_delegations(
+    conn: &DbConn,
+    voting: &Voting,
+    poll: &mut PollResult,
+    options: &mut [PollOptionResult],
+    ballots: &mut Vec<Ballot>,
+) -> Result<DelegationResult, ErrorResponse> {
+    // Secret ballots can not be assigned to a delegate.
+    let delegations = match &poll.topic {
+        Some(topic) if !voting.secret => find_delegations(conn, &voting.id, topic)?,
+        _ => Vec::new(),
+    };
+    if delegations.is_empty() {
+        return Ok(DelegationResult {
+            resolved: Vec::new(),
+            cycles: Vec::new(),
+        });
+    }
+
+    let mut choices_of_voter: HashMap<String, Vec<VoteChoice>> = HashMap::new();
+    for (choice, named_vote) in find_poll_vote_choices(conn, &poll.id)? {
+        if let Some(named_vote) = named_vote {
+            choices_of_voter
+                .entry(named_vote.voter_fk)
+                .or_default()
+                .push(choice);
+        }
+    }
+    let direct_voters = choices_of_voter
+        .keys()
+        .cloned()
+        .collect::<HashSet<String>>();
+    let mut result = delegation::resolve(
+        &delegations
             .into_iter()
-            .map(|poll| get_voting_dto::GetVotingPollsResponse {
-                status: get_status_from_poll(&poll),
-                poll_id: poll.id,
-                name: poll.name,
-                description: poll.description,
-                votes_accept: poll.votes_accept,
-                votes_decline: poll.votes_decline,
-                votes_abstain: poll.votes_abstain,
-                votes_total: poll.votes_total,
-            })
-            .collect::<Vec<get_voting_dto::GetVotingPollsResponse>>()
-    })
+            .map(|delegation| (delegation.voter_fk, delegation.delegate_voter_fk))
+            .collect::<Vec<(String, String)>>(),
+        &direct_voters,
+    );
+
+    // Delegations only count for voters of the voting who are not revoked.
+    let weight_of_voter = find_voters(conn, &voting.id)?
+        .into_iter()
+        .filter(|voter| voter.revoked_at.is_none())
+        .map(|voter| (voter.id, voter.weight as i64))
+        .collect::<HashMap<String, i64>>();
+    result
+        .resolved
+        .retain(|(voter_id, _)| weight_of_voter.contains_key(voter_id));
+    for (voter_id, delegate_id) in &result.resolved {
+        let weight = weight_of_voter[voter_id];
+        let choices = &choices_of_voter[delegate_id];
+
+        poll.votes_total += 1;
+        poll.weighted_votes_total += weight;
+        for choice in choices.iter().filter(|choice| choice.rank == 0) {
+            let option = match options
+                .iter_mut()
+                .find(|option| option.id == choice.poll_option_fk)
+            {
+                Some(option) => option,
+                None => continue,
+            };
+            let score = choice.score.unwrap_or(0) as i64;
+            option.votes += 1;
+            option.weighted_votes += weight;
+            option.score_total += score;
+            option.weighted_score_total += score * weight;
+            match option.preset_answer {
+                Some(PresetAnswer::Accept) => {
+                    poll.votes_accept += 1;
+                    poll.weighted_votes_accept += weight;
+                }
+                Some(PresetAnswer::Decline) => {
+                    poll.votes_decline += 1;
+                    poll.weighted_votes_decline += weight;
+                }
+                Some(PresetAnswer::Abstain) => {
+                    poll.votes_abstain += 1;
+                    poll.weighted_votes_abstain += weight;
+                }
+                None => {}
+            }
+        }
+        if poll.poll_type.is_ranked() {
+            ballots.push(Ballot {
+                choices: choices
+                    .iter()
+                    .map(|choice| choice.poll_option_fk.to_string())
+                    .collect(),
+                weight: match poll.vote_counting {
+                    VoteCounting::Heads => 1,
+                    VoteCounting::Weights => weight,
+                },
+            });
+        }
+    }
+
+    Ok(result)
 }
 
-fn get_status_from_poll(poll: &PollResult) -> String {
+fn get_instant_runoff_response(
+    result: InstantRunoffResult,
+) -> get_voting_dto::GetVotingInstantRunoffResponse {
+    get_voting_dto::GetVotingInstantRunoffResponse {
+        rounds: result
+            .rounds
+            .into_iter()
+            .map(
+                |round| get_voting_dto::GetVotingInstantRunoffRoundResponse {
+                    votes: round
+                        .votes
+                        .into_iter()
+                        .map(|(option_id, votes)| {
+                            get_voting_dto::GetVotingInstantRunoffVotesResponse { option_id, votes }
+                        })
+                        .collect(),
+                    exhausted: round.exhausted,
+                    eliminated_option_id: round.eliminated,
+                },
+            )
+            .collect(),
+        winner_option_id: result.winner,
+    }
+}
+
+fn get_schulze_response(result: SchulzeResult) -> get_voting_dto::GetVotingSchulzeResponse {
+    get_voting_dto::GetVotingSchulzeResponse {
+        option_ids: result.options,
+        pairwise_preferences: result.pairwise_preferences,
+        strongest_paths: result.strongest_paths,
+        ranking: result
+            .ranking
+            .into_iter()
+            .map(
+                |(option_id, rank)| get_voting_dto::GetVotingSchulzeRankingResponse {
+                    option_id,
+                    rank,
+                },
+            )
+            .collect(),
+        winner_option_id: result.winner,
+    }
+}
+
+fn get_status_from_poll(
+    poll: &PollResult,
+    options: &[&PollOptionResult],
+    electorate: i64,
+    instant_runoff: Option<&InstantRunoffResult>,
+    schulze: Option<&SchulzeResult>,
+) -> (String, Option<String>) {
     if poll.votes_total == 0 {
-        return String::from("NOT_VOTED");
+        return (String::from("NOT_VOTED"), None);
+    }
+    let votes = count_poll_votes(poll);
+    if !majority::is_quorum_met(poll.quorum_percent, votes.total, electorate) {
+        return (String::from("QUORUM_NOT_MET"), None);
+    }
+    match poll.poll_type {
+        PollType::YesNo => {
+            let winner_option = |preset_answer: PresetAnswer| {
+                options
+                    .iter()
+                    .find(|option| option.preset_answer == Some(preset_answer))
+                    .map(|option| option.id.to_string())
+            };
+            let votes_cast = votes.accept + votes.decline;
+            if votes.accept > votes.decline
+                && majority::has_majority(poll.majority_rule, votes.accept, votes_cast, electorate)
+            {
+                return (
+                    String::from("ACCEPTED"),
+                    winner_option(PresetAnswer::Accept),
+                );
+            }
+            if votes.decline > votes.accept || poll.majority_rule != MajorityRule::Simple {
+                return (
+                    String::from("DECLINED"),
+                    winner_option(PresetAnswer::Decline),
+                );
+            }
+            (String::from("DRAW"), None)
+        }
+        PollType::SingleChoice | PollType::Approval => {
+            match find_leader(options, |option| count_option_votes(poll, option)) {
+                Some(leader)
+                    if poll.majority_rule != MajorityRule::Simple
+                        && !majority::has_majority(
+                            poll.majority_rule,
+                            count_option_votes(poll, leader),
+                            votes.total - votes.abstain,
+                            electorate,
+                        ) =>
+                {
+                    (String::from("NO_MAJORITY"), None)
+                }
+                leader => get_status_from_winner(leader.map(|leader| &leader.id)),
+            }
+        }
+        PollType::Score => get_status_from_winner(
+            find_leader(options, |option| count_option_score(poll, option))
+                .map(|leader| &leader.id),
+        ),
+        PollType::InstantRunoff => {
+            get_status_from_winner(instant_runoff.and_then(|result| result.winner.as_ref()))
+        }
+        PollType::Schulze => {
+            get_status_from_winner(schulze.and_then(|result| result.winner.as_ref()))
+        }
+    }
+}
+
+/// Votes of a poll as counted by its vote counting.
+struct PollVotes {
+    accept: i64,
+    decline: i64,
+    abstain: i64,
+    total: i64,
+}
+
+fn count_poll_votes(poll: &PollResult) -> PollVotes {
+    match poll.vote_counting {
+        VoteCounting::Heads => PollVotes {
+            accept: poll.votes_accept,
+            decline: poll.votes_decline,
+            abstain: poll.votes_abstain,
+            total: poll.votes_total,
+        },
+        VoteCounting::Weights => PollVotes {
+            accept: poll.weighted_votes_accept,
+            decline: poll.weighted_votes_decline,
+            abstain: poll.weighted_votes_abstain,
+            total: poll.weighted_votes_total,
+        },
+    }
+}
+
+fn count_option_votes(poll: &PollResult, option: &PollOptionResult) -> i64 {
+    match poll.vote_counting {
+        VoteCounting::Heads => option.votes,
+        VoteCounting::Weights => option.weighted_votes,
+    }
+}
+
+fn count_option_score(poll: &PollResult, option: &PollOptionResult) -> i64 {
+    match poll.vote_counting {
+        VoteCounting::Heads => option.score_total,
+        VoteCounting::Weights => option.weighted_score_total,
+    }
+}
+
+/// The option with the highest value leads, there is no leader when several
+/// options share the highest value.
+fn find_leader<'a>(
+    options: &[&'a PollOptionResult],
+    value: impl Fn(&PollOptionResult) -> i64,
+) -> Option<&'a PollOptionResult> {
+    match find_leaders(options, value).as_slice() {
+        [leader] => Some(leader),
+        _ => None,
     }
-    if poll.votes_accept > poll.votes_decline {
-        return String::from("ACCEPTED");
+}
+
+fn find_leaders<'a>(
+    options: &[&'a PollOptionResult],
+    value: impl Fn(&PollOptionResult) -> i64,
+) -> Vec<&'a PollOptionResult> {
+    let highest_value = options.iter().map(|option| value(option)).max();
+    options
+        .iter()
+        .filter(|option| Some(value(option)) == highest_value)
+        .copied()
+        .collect()
+}
+
+/// The options a poll with the status `DRAW` is tied between.
+fn find_tied_option_ids(
+    poll: &PollResult,
+    options: &[&PollOptionResult],
+    instant_runoff: Option<&InstantRunoffResult>,
+    schulze: Option<&SchulzeResult>,
+) -> Vec<String> {
+    match poll.poll_type {
+        PollType::YesNo => options
+            .iter()
+            .filter(|option| option.preset_answer != Some(PresetAnswer::Abstain))
+            .map(|option| option.id.to_string())
+            .collect(),
+        PollType::SingleChoice | PollType::Approval => {
+            find_leaders(options, |option| count_option_votes(poll, option))
+                .iter()
+                .map(|option| option.id.to_string())
+                .collect()
+        }
+        PollType::Score => find_leaders(options, |option| count_option_score(poll, option))
+            .iter()
+            .map(|option| option.id.to_string())
+            .collect(),
+        PollType::InstantRunoff => instant_runoff
+            .and_then(|result| result.rounds.last())
+            .map(|round| {
+                round
+                    .votes
+                    .iter()
+                    .map(|(option_id, _)| option_id.to_string())
+                    .collect()
+            })
+            .unwrap_or_default(),
+        PollType::Schulze => schulze
+            .map(|result| {
+                result
+                    .ranking
+                    .iter()
+                    .filter(|(_, rank)| *rank == 1)
+                    .map(|(option_id, _)| option_id.to_string())
+                    .collect()
+            })
+            .unwrap_or_default(),
+    }
+}
+
+/// Applies the tie policy of the voting to a tied poll. Returns the new
+/// status and winner together with how the tie was resolved, or nothing when
+/// the tie stays unresolved.
+fn resolve_tie(
+    conn: &DbConn,
+    voting: &Voting,
+    poll: &PollResult,
+    options: &[&PollOptionResult],
+    tied_option_ids: &[String],
+) -> Result<Option<(String, Option<String>, String)>, ErrorResponse> {
+    if tied_option_ids.is_empty() {
+        return Ok(None);
     }
-    if poll.votes_decline > poll.votes_accept {
-        return String::from("DECLINED");
+    let (winner_option_id, tie_resolution) = match voting.tie_policy {
+        TiePolicy::Draw => return Ok(None),
+        TiePolicy::Reject => {
+            let (status, winner_option_id) = match poll.poll_type {
+                PollType::YesNo => (
+                    String::from("DECLINED"),
+                    options
+                        .iter()
+                        .find(|option| option.preset_answer == Some(PresetAnswer::Decline))
+                        .map(|option| option.id.to_string()),
+                ),
+                _ => (String::from("REJECTED"), None),
+            };
+            return Ok(Some((status, winner_option_id, String::from("REJECTED"))));
+        }
+        TiePolicy::ChairDecides => (
+            poll.chair_decision_option_fk
+                .as_ref()
+                .filter(|option_id| tied_option_ids.contains(option_id))
+                .map(|option_id| option_id.to_string()),
+            "CHAIR_DECISION",
+        ),
+        TiePolicy::CastingVoter => (
+            find_casting_vote(conn, voting, poll, tied_option_ids)?,
+            "CASTING_VOTE",
+        ),
+    };
+
+    Ok(winner_option_id.map(|winner_option_id| {
+        let preset_answer = options
+            .iter()
+            .find(|option| option.id == winner_option_id)
+            .and_then(|option| option.preset_answer);
+        let status = match preset_answer {
+            Some(PresetAnswer::Accept) => "ACCEPTED",
+            Some(_) => "DECLINED",
+            None => "DECIDED",
+        };
+        (
+            String::from(status),
+            Some(winner_option_id),
+            String::from(tie_resolution),
+        )
+    }))
+}
+
+/// The tied option the casting voter prefers: the one ranked highest, or
+/// scored highest on `SCORE` polls. Nothing when the casting voter did not
+/// vote or does not prefer a single one of the tied options.
+fn find_casting_vote(
+    conn: &DbConn,
+    voting: &Voting,
+    poll: &PollResult,
+    tied_option_ids: &[String],
+) -> Result<Option<String>, ErrorResponse> {
+    let vote = match &voting.casting_voter_fk {
+        Some(voter_id) => find_vote(conn, &poll.id, voter_id)?,
+        None => None,
+    };
+    let choices = match vote {
+        Some(vote) => find_vote_choices(conn, &vote.vote_fk)?,
+        None => return Ok(None),
+    };
+
+    let preference = |choice: &VoteChoice| match poll.poll_type {
+        PollType::Score => -choice.score.unwrap_or(0),
+        _ => choice.rank,
+    };
+    let tied_choices = choices
+        .iter()
+        .filter(|choice| tied_option_ids.contains(&choice.poll_option_fk))
+        .collect::<Vec<&VoteChoice>>();
+    let best_preference = tied_choices.iter().map(|choice| preference(choice)).min();
+    let preferred_choices = tied_choices
+        .into_iter()
+        .filter(|choice| Some(preference(choice)) == best_preference)
+        .collect::<Vec<&VoteChoice>>();
+
+    Ok(match preferred_choices.as_slice() {
+        [choice] => Some(choice.poll_option_fk.to_string()),
+        _ => None,
+    })
+}
+
+fn get_status_from_winner(winner: Option<&String>) -> (String, Option<String>) {
+    match winner {
+        Some(winner) => (String::from("DECIDED"), Some(winner.to_string())),
+        None => (String::from("DRAW"), None),
     }
-    String::from("DRAW")
 }
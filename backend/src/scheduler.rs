@@ -0,0 +1,134 @@
+//! Opens and closes polls at their `opens_at` and `closes_at` times and moves
+//! auto-advancing agendas on. Archived votings are purged once they are older
+//! than the retention period.
+//!
+//! A background thread applies due schedules periodically. Requests that
+//! depend on the state of a poll apply the schedule of their voting first, so
+//! they never see a poll that should already be open or closed.
+
+use crate::actions::delete::*;
+use crate::actions::find::*;
+use crate::actions::update::*;
+use crate::models::{Poll, Voting};
+use crate::pool::{DbConn, Pool};
+use crate::routes::agenda::move_agenda;
+use crate::routes::voting::finalize_poll;
+use crate::types::PollState;
+use crate::utils::ErrorResponse;
+
+use chrono::{NaiveDateTime, Utc};
+use std::thread;
+use std::time::Duration;
+
+pub fn start(pool: Pool, interval: Duration, archive_retention: chrono::Duration) {
+    thread::spawn(move || loop {
+        thread::sleep(interval);
+        match pool.get() {
+            Ok(conn) => {
+                let conn = DbConn(conn);
+                if let Err(err) = apply_due_schedules(&conn) {
+                    println!("Could not apply poll schedules. err: {}", err.reason);
+                }
+                let archived_before = Utc::now().naive_utc() - archive_retention;
+                if let Err(err) = delete_votings_archived_before(&conn, archived_before) {
+                    println!("Could not purge archived votings. err: {}", err.reason);
+                }
+            }
+            Err(err) => println!("Could not get connection for scheduler. err: {:?}", err),
+        }
+    });
+}
+
+/// A voting whose schedule can not be applied does not hold up the others.
+fn apply_due_schedules(conn: &DbConn) -> Result<(), ErrorResponse> {
+    let now = Utc::now().naive_utc();
+    for voting in find_votings_with_due_polls(conn, now)? {
+        if let Err(err) = apply_schedule(conn, &voting) {
+            println!(
+                "Could not apply poll schedule of voting with id: {}. err: {}",
+                voting.id, err.reason
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Opens the draft polls of the voting whose `opens_at` passed and closes the
+/// open polls whose `closes_at` passed, recording their final result.
+pub fn apply_schedule(conn: &DbConn, voting: &Voting) -> Result<(), ErrorResponse> {
+    if voting.archived_at.is_some() {
+        return Ok(());
+    }
+    let now = Utc::now().naive_utc();
+    let is_due = |at: Option<NaiveDateTime>| matches!(at, Some(at) if at <= now);
+
+    for poll in find_polls(conn, &voting.id)? {
+        let mut state = poll.state;
+        if state == PollState::Draft && is_due(poll.opens_at) {
+            update_poll_state(conn, &poll, PollState::Open, now)?;
+            state = PollState::Open;
+        }
+        if state == PollState::Open && is_due(poll.closes_at) {
+            finalize_poll(conn, voting, &poll, now)?;
+        }
+    }
+
+    match (voting.auto_advance, voting.agenda_poll_index) {
+        (true, Some(agenda_poll_index)) => advance_agenda(conn, voting, agenda_poll_index, now),
+        _ => Ok(()),
+    }
+}
+
+/// Moves the agenda to the next poll that is not closed once the current poll
+/// was closed, every voter voted on it or its time limit ran out. Without such
+/// a poll the current poll of the agenda is closed instead.
+fn advance_agenda(
+    conn: &DbConn,
+    voting: &Voting,
+    agenda_poll_index: i32,
+    now: NaiveDateTime,
+) -> Result<(), ErrorResponse> {
+    let poll = find_poll_at_index(conn, voting, agenda_poll_index)?;
+    let finished = match poll.state {
+        PollState::Draft => false,
+        PollState::Open => is_poll_finished(conn, voting, &poll, now)?,
+        PollState::Closed => true,
+    };
+    if !finished {
+        return Ok(());
+    }
+
+    let next_poll_index = find_polls(conn, &voting.id)?
+        .iter()
+        .enumerate()
+        .skip(agenda_poll_index as usize + 1)
+        .find(|(_, next_poll)| next_poll.state != PollState::Closed)
+        .map(|(index, _)| index as i32);
+    match next_poll_index {
+        Some(next_poll_index) => move_agenda(conn, voting, next_poll_index),
+        None if poll.state == PollState::Open => finalize_poll(conn, voting, &poll, now),
+        None => Ok(()),
+    }
+}
+
+/// Every voter voted on the open poll or its time limit ran out.
+fn is_poll_finished(
+    conn: &DbConn,
+    voting: &Voting,
+    poll: &Poll,
+    now: NaiveDateTime,
+) -> Result<bool, ErrorResponse> {
+    let voter_count = match (poll.present_voters_only, poll.opened_at) {
+        (true, Some(opened_at)) => find_eligible_voters(conn, &voting.id, opened_at)?.len() as i32,
+        _ => find_amount_of_voters(conn, &voting.id)?,
+    };
+    let everyone_voted =
+        voter_count > 0 && find_amount_of_participations(conn, &poll.id)? >= voter_count;
+    let time_ran_out = match (poll.opened_at, poll.time_limit_seconds) {
+        (Some(opened_at), Some(time_limit_seconds)) => {
+            opened_at + chrono::Duration::seconds(time_limit_seconds as i64) <= now
+        }
+        _ => false,
+    };
+    Ok(everyone_voted || time_ran_out)
+}
@@ -1,3 +1,64 @@
+table! {
+    admin_keys (id) {
+        id -> Varchar,
+        voting_fk -> Varchar,
+        key_hash -> Varchar,
+        label -> Varchar,
+        scope -> Varchar,
+        created_at -> Timestamp,
+    }
+}
+
+table! {
+    attendances (id) {
+        id -> Varchar,
+        voting_fk -> Varchar,
+        voter_fk -> Varchar,
+        checked_in_at -> Timestamp,
+        checked_out_at -> Nullable<Timestamp>,
+    }
+}
+
+table! {
+    delegations (id) {
+        id -> Varchar,
+        voting_fk -> Varchar,
+        voter_fk -> Varchar,
+        delegate_voter_fk -> Varchar,
+        topic -> Varchar,
+    }
+}
+
+table! {
+    named_votes (vote_fk) {
+        vote_fk -> Varchar,
+        poll_fk -> Varchar,
+        voter_fk -> Varchar,
+        weight -> Int4,
+        voted_at -> Timestamp,
+        replaced_at -> Nullable<Timestamp>,
+    }
+}
+
+table! {
+    participations (id) {
+        id -> Varchar,
+        poll_fk -> Varchar,
+        voter_fk -> Varchar,
+        proxy_voter_fk -> Nullable<Varchar>,
+    }
+}
+
+table! {
+    poll_options (id) {
+        id -> Varchar,
+        sequenz_number -> Int4,
+        poll_fk -> Varchar,
+        name -> Varchar,
+        preset_answer -> Nullable<Varchar>,
+    }
+}
+
 table! {
     polls (id) {
         id -> Varchar,
@@ -5,6 +66,33 @@ table! {
         voting_fk -> Varchar,
         name -> Varchar,
         description -> Varchar,
+        poll_type -> Varchar,
+        min_approvals -> Nullable<Int4>,
+        max_approvals -> Nullable<Int4>,
+        max_score -> Nullable<Int4>,
+        majority_rule -> Varchar,
+        quorum_percent -> Nullable<Int4>,
+        chair_decision_option_fk -> Nullable<Varchar>,
+        vote_counting -> Varchar,
+        topic -> Nullable<Varchar>,
+        state -> Varchar,
+        opened_at -> Nullable<Timestamp>,
+        closed_at -> Nullable<Timestamp>,
+        opens_at -> Nullable<Timestamp>,
+        closes_at -> Nullable<Timestamp>,
+        final_status -> Nullable<Varchar>,
+        final_winner_option_fk -> Nullable<Varchar>,
+        time_limit_seconds -> Nullable<Int4>,
+        present_voters_only -> Bool,
+    }
+}
+
+table! {
+    proxies (id) {
+        id -> Varchar,
+        voting_fk -> Varchar,
+        voter_fk -> Varchar,
+        proxy_voter_fk -> Varchar,
     }
 }
 
@@ -14,6 +102,28 @@ table! {
         voter_key_hash -> Varchar,
         voting_fk -> Varchar,
         username -> Varchar,
+        weight -> Int4,
+        email -> Nullable<Varchar>,
+        revoked_at -> Nullable<Timestamp>,
+    }
+}
+
+table! {
+    vote_choices (id) {
+        id -> Varchar,
+        vote_fk -> Varchar,
+        poll_option_fk -> Varchar,
+        rank -> Int4,
+        score -> Nullable<Int4>,
+    }
+}
+
+table! {
+    voting_templates (id) {
+        id -> Varchar,
+        name -> Varchar,
+        admin_key_hash -> Varchar,
+        definition -> Text,
     }
 }
 
@@ -21,23 +131,55 @@ table! {
     votes (id) {
         id -> Varchar,
         poll_fk -> Varchar,
-        voter_fk -> Varchar,
-        answer -> Nullable<Bool>,
     }
 }
 
 table! {
     votings (id) {
         id -> Varchar,
-        admin_key_hash -> Varchar,
         name -> Varchar,
-        active_poll_index -> Nullable<Int4>,
+        tie_policy -> Varchar,
+        casting_voter_fk -> Nullable<Varchar>,
+        max_proxies_per_voter -> Int4,
+        secret -> Bool,
+        allow_vote_change -> Bool,
+        max_vote_changes -> Nullable<Int4>,
+        agenda_poll_index -> Nullable<Int4>,
+        auto_advance -> Bool,
+        archived_at -> Nullable<Timestamp>,
+        recovery_secret_hash -> Nullable<Varchar>,
     }
 }
 
+joinable!(admin_keys -> votings (voting_fk));
+joinable!(attendances -> voters (voter_fk));
+joinable!(attendances -> votings (voting_fk));
+joinable!(delegations -> votings (voting_fk));
+joinable!(named_votes -> polls (poll_fk));
+joinable!(named_votes -> voters (voter_fk));
+joinable!(named_votes -> votes (vote_fk));
+joinable!(participations -> polls (poll_fk));
+joinable!(poll_options -> polls (poll_fk));
 joinable!(polls -> votings (voting_fk));
+joinable!(proxies -> votings (voting_fk));
 joinable!(voters -> votings (voting_fk));
+joinable!(vote_choices -> poll_options (poll_option_fk));
+joinable!(vote_choices -> named_votes (vote_fk));
+joinable!(vote_choices -> votes (vote_fk));
 joinable!(votes -> polls (poll_fk));
-joinable!(votes -> voters (voter_fk));
 
-allow_tables_to_appear_in_same_query!(polls, voters, votes, votings,);
+allow_tables_to_appear_in_same_query!(
+    admin_keys,
+    attendances,
+    delegations,
+    named_votes,
+    participations,
+    poll_options,
+    polls,
+    proxies,
+    vote_choices,
+    voters,
+    votes,
+    voting_templates,
+    votings,
+);
@@ -5,9 +5,44 @@ table! {
         voting_fk -> Varchar,
         name -> Varchar,
         description -> Varchar,
+        poll_type -> Varchar,
+        majority_rule -> Varchar,
+        quorum_percent -> Nullable<Int4>,
+        chair_decision_option_fk -> Nullable<Varchar>,
+        vote_counting -> Varchar,
+        topic -> Nullable<Varchar>,
+        state -> Varchar,
+        opened_at -> Nullable<Timestamp>,
+        closed_at -> Nullable<Timestamp>,
+        opens_at -> Nullable<Timestamp>,
+        closes_at -> Nullable<Timestamp>,
+        final_status -> Nullable<Varchar>,
+        final_winner_option_fk -> Nullable<Varchar>,
+        time_limit_seconds -> Nullable<Int4>,
+        present_voters_only -> Bool,
         votes_accept -> Int8,
         votes_decline -> Int8,
         votes_abstain -> Int8,
         votes_total -> Int8,
+        weighted_votes_accept -> Int8,
+        weighted_votes_decline -> Int8,
+        weighted_votes_abstain -> Int8,
+        weighted_votes_total -> Int8,
+        proxy_votes -> Int8,
+    }
+}
+
+table! {
+    poll_option_results (id) {
+        id -> Varchar,
+        sequenz_number -> Int4,
+        poll_fk -> Varchar,
+        voting_fk -> Varchar,
+        name -> Varchar,
+        preset_answer -> Nullable<Varchar>,
+        votes -> Int8,
+        score_total -> Int8,
+        weighted_votes -> Int8,
+        weighted_score_total -> Int8,
     }
 }
@@ -0,0 +1,30 @@
+pub mod delegation;
+pub mod instant_runoff;
+pub mod majority;
+pub mod schulze;
+
+/// A ranked ballot. `choices` lists option ids from the most to the least
+/// preferred one and the ballot counts `weight` times.
+#[derive(Debug, PartialEq)]
+pub struct Ballot {
+    pub choices: Vec<String>,
+    pub weight: i64,
+}
+
+/// Fixtures for the tests of the tally modules. Options are named by single
+/// characters, so `"ABC"` lists the options `A`, `B` and `C`.
+#[cfg(test)]
+pub mod test_helpers {
+    use super::Ballot;
+
+    pub fn options(names: &str) -> Vec<String> {
+        names.chars().map(|name| name.to_string()).collect()
+    }
+
+    pub fn ballot(choices: &str, weight: i64) -> Ballot {
+        Ballot {
+            choices: options(choices),
+            weight,
+        }
+    }
+}
@@ -0,0 +1,66 @@
+//! Resolution of transitive delegations on one topic.
+//!
+//! A voter who did not vote follows their delegation chain until it reaches a
+//! voter who voted directly, whose ballot then also counts for them. A direct
+//! vote always overrides the voter's own delegation. Chains ending at a voter
+//! who neither voted nor delegated do not count. Chains running into a cycle
+//! do not count either and the cycle is reported.
+
+use std::collections::{HashMap, HashSet};
+
+#[derive(Debug, PartialEq)]
+pub struct DelegationResult {
+    /// Voters counted through delegation together with the voter whose
+    /// ballot counts for them.
+    pub resolved: Vec<(String, String)>,
+    /// Every cycle once, starting at its smallest voter id and listed in
+    /// delegation order.
+    pub cycles: Vec<Vec<String>>,
+}
+
+/// `delegations` are pairs of a voter and their delegate.
+pub fn resolve(
+    delegations: &[(String, String)],
+    direct_voters: &HashSet<String>,
+) -> DelegationResult {
+    let delegate_of = delegations
+        .iter()
+        .map(|(voter, delegate)| (voter, delegate))
+        .collect::<HashMap<&String, &String>>();
+
+    let mut resolved = Vec::new();
+    let mut cycles: Vec<Vec<String>> = Vec::new();
+    for (voter, _) in delegations {
+        if direct_voters.contains(voter) {
+            continue;
+        }
+        let mut chain = vec![voter];
+        while let Some(delegate) = delegate_of.get(chain[chain.len() - 1]) {
+            if direct_voters.contains(*delegate) {
+                resolved.push((voter.to_string(), delegate.to_string()));
+                break;
+            }
+            if let Some(position) = chain.iter().position(|voter| voter == delegate) {
+                let cycle = get_normalized_cycle(&chain[position..]);
+                if !cycles.contains(&cycle) {
+                    cycles.push(cycle);
+                }
+                break;
+            }
+            chain.push(delegate);
+        }
+    }
+
+    DelegationResult { resolved, cycles }
+}
+
+fn get_normalized_cycle(cycle: &[&String]) -> Vec<String> {
+    let start = (0..cycle.len())
+        .min_by_key(|&i| cycle[i])
+        .unwrap_or_default();
+    cycle[start..]
+        .iter()
+        .chain(cycle[..start].iter())
+        .map(|voter| voter.to_string())
+        .collect()
+}
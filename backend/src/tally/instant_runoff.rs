@@ -0,0 +1,178 @@
+//! Instant-runoff tally of ranked ballots.
+//!
+//! Every round counts each ballot for its highest ranked option that is still
+//! in the race, with the weight of the ballot. Ballots without such an option
+//! are exhausted. An option with more than half of the votes of the ballots
+//! that are not exhausted wins, otherwise the option with the fewest votes is
+//! eliminated and the next round starts.
+//!
+//! Ties for elimination are broken by looking back through the previous
+//! rounds: the tied option with fewer votes in the latest round in which the
+//! tied options differed is eliminated. If they were tied in every round, the
+//! option listed last on the poll is eliminated. When all remaining options
+//! are tied none of them can be eliminated and the tally ends without a winner.
+
+use crate::tally::Ballot;
+
+#[derive(Debug, PartialEq)]
+pub struct InstantRunoffRound {
+    /// Votes of every option still in the race, in poll order.
+    pub votes: Vec<(String, i64)>,
+    pub exhausted: i64,
+    pub eliminated: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct InstantRunoffResult {
+    pub rounds: Vec<InstantRunoffRound>,
+    pub winner: Option<String>,
+}
+
+/// Runs the instant-runoff elimination. `options` are the option ids in poll
+/// order.
+pub fn tally(options: &[String], ballots: &[Ballot]) -> InstantRunoffResult {
+    let mut continuing = options.to_vec();
+    let mut rounds: Vec<InstantRunoffRound> = Vec::new();
+
+    loop {
+        let mut votes = continuing
+            .iter()
+            .map(|option| (option.to_string(), 0))
+            .collect::<Vec<(String, i64)>>();
+        let mut exhausted = 0;
+        for ballot in ballots {
+            match ballot
+                .choices
+                .iter()
+                .find_map(|choice| continuing.iter().position(|option| option == choice))
+            {
+                Some(position) => votes[position].1 += ballot.weight,
+                None => exhausted += ballot.weight,
+            }
+        }
+
+        let active = votes.iter().map(|(_, count)| count).sum::<i64>();
+        let winner = votes
+            .iter()
+            .find(|(_, count)| count * 2 > active)
+            .map(|(option, _)| option.to_string());
+        let fewest = votes.iter().map(|(_, count)| *count).min().unwrap_or(0);
+        let tied = votes
+            .iter()
+            .filter(|(_, count)| *count == fewest)
+            .map(|(option, _)| option.to_string())
+            .collect::<Vec<String>>();
+
+        if winner.is_some() || tied.len() == continuing.len() {
+            rounds.push(InstantRunoffRound {
+                votes,
+                exhausted,
+                eliminated: None,
+            });
+            return InstantRunoffResult { rounds, winner };
+        }
+
+        let eliminated = break_elimination_tie(tied, &rounds, options);
+        continuing.retain(|option| option != &eliminated);
+        rounds.push(InstantRunoffRound {
+            votes,
+            exhausted,
+            eliminated: Some(eliminated),
+        });
+    }
+}
+
+fn break_elimination_tie(
+    mut tied: Vec<String>,
+    rounds: &[InstantRunoffRound],
+    options: &[String],
+) -> String {
+    for round in rounds.iter().rev() {
+        if tied.len() == 1 {
+            break;
+        }
+        let votes_of = |tied_option: &String| {
+            round
+                .votes
+                .iter()
+                .find(|(option, _)| option == tied_option)
+                .map_or(0, |(_, count)| *count)
+        };
+        let fewest = tied.iter().map(votes_of).min().unwrap_or(0);
+        tied.retain(|option| votes_of(option) == fewest);
+    }
+
+    tied.into_iter()
+        .max_by_key(|tied_option| options.iter().position(|option| option == tied_option))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tally::test_helpers::{ballot, options};
+
+    fn votes(counts: &[(&str, i64)]) -> Vec<(String, i64)> {
+        counts
+            .iter()
+            .map(|(option, count)| (option.to_string(), *count))
+            .collect()
+    }
+
+    #[test]
+    fn breaks_an_elimination_tie_by_the_previous_rounds() {
+        let ballots = vec![
+            ballot("A", 4),
+            ballot("B", 2),
+            ballot("C", 3),
+            ballot("DB", 1),
+        ];
+
+        let result = tally(&options("ABCD"), &ballots);
+
+        assert_eq!(
+            result.rounds[1],
+            InstantRunoffRound {
+                votes: votes(&[("A", 4), ("B", 3), ("C", 3)]),
+                exhausted: 0,
+                eliminated: Some("B".to_string()),
+            }
+        );
+        assert_eq!(result.winner, Some("A".to_string()));
+    }
+
+    #[test]
+    fn eliminates_the_option_listed_last_when_tied_in_every_round() {
+        let ballots = vec![ballot("A", 3), ballot("B", 2), ballot("C", 2)];
+
+        let result = tally(&options("ABC"), &ballots);
+
+        assert_eq!(result.rounds[0].eliminated, Some("C".to_string()));
+        assert_eq!(
+            result.rounds[1],
+            InstantRunoffRound {
+                votes: votes(&[("A", 3), ("B", 2)]),
+                exhausted: 2,
+                eliminated: None,
+            }
+        );
+        assert_eq!(result.winner, Some("A".to_string()));
+    }
+
+    #[test]
+    fn ends_without_a_winner_when_all_options_are_tied() {
+        let ballots = vec![ballot("AB", 1), ballot("BA", 1)];
+
+        let result = tally(&options("AB"), &ballots);
+
+        assert_eq!(
+            result.rounds,
+            vec![InstantRunoffRound {
+                votes: votes(&[("A", 1), ("B", 1)]),
+                exhausted: 0,
+                eliminated: None,
+            }]
+        );
+        assert_eq!(result.winner, None);
+    }
+}
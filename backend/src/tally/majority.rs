@@ -0,0 +1,53 @@
+//! Quorum and majority checks on top of the counted votes.
+
+use crate::types::MajorityRule;
+
+/// The quorum is the minimum turnout in percent of all voters of a voting.
+/// Abstentions count towards the turnout.
+pub fn is_quorum_met(quorum_percent: Option<i32>, votes_total: i64, voter_count: i64) -> bool {
+    match quorum_percent {
+        Some(quorum_percent) => votes_total * 100 >= quorum_percent as i64 * voter_count,
+        None => true,
+    }
+}
+
+/// Whether `votes` for the winning option satisfy the majority rule.
+/// `votes_cast` are all votes that are not abstentions.
+pub fn has_majority(rule: MajorityRule, votes: i64, votes_cast: i64, voter_count: i64) -> bool {
+    match rule {
+        MajorityRule::Simple => votes * 2 > votes_cast,
+        MajorityRule::Absolute => votes * 2 > voter_count,
+        MajorityRule::TwoThirds => votes > 0 && votes * 3 >= votes_cast * 2,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn meets_the_quorum_from_exactly_the_quorum_percent() {
+        assert!(is_quorum_met(Some(50), 5, 10));
+        assert!(!is_quorum_met(Some(50), 4, 10));
+        assert!(is_quorum_met(Some(34), 2, 3));
+        assert!(!is_quorum_met(Some(34), 1, 3));
+        assert!(is_quorum_met(None, 0, 10));
+    }
+
+    #[test]
+    fn needs_two_thirds_of_the_votes_cast() {
+        assert!(has_majority(MajorityRule::TwoThirds, 2, 3, 10));
+        assert!(!has_majority(MajorityRule::TwoThirds, 1, 2, 10));
+        assert!(has_majority(MajorityRule::TwoThirds, 67, 100, 100));
+        assert!(!has_majority(MajorityRule::TwoThirds, 66, 100, 100));
+        assert!(!has_majority(MajorityRule::TwoThirds, 0, 0, 10));
+    }
+
+    #[test]
+    fn needs_more_than_half_for_simple_and_absolute_majorities() {
+        assert!(has_majority(MajorityRule::Simple, 6, 10, 20));
+        assert!(!has_majority(MajorityRule::Simple, 5, 10, 20));
+        assert!(has_majority(MajorityRule::Absolute, 6, 8, 10));
+        assert!(!has_majority(MajorityRule::Absolute, 5, 8, 10));
+    }
+}
@@ -0,0 +1,181 @@
+//! Schulze tally of ranked ballots.
+//!
+//! A ballot prefers every ranked option over the options ranked below it and
+//! over all options it does not rank. Options a ballot does not rank are
+//! preferred equally. The strength of the strongest path between two options
+//! is found with a widest path variant of the Floyd–Warshall algorithm. An
+//! option is ranked above another one when its strongest path to the other
+//! option is stronger than the path in the opposite direction.
+
+use crate::tally::Ballot;
+
+#[derive(Debug, PartialEq)]
+pub struct SchulzeResult {
+    /// Option ids in poll order, the row and column order of both matrices.
+    pub options: Vec<String>,
+    /// `pairwise_preferences[i][j]` is the weight of the ballots preferring
+    /// option `i` over option `j`.
+    pub pairwise_preferences: Vec<Vec<i64>>,
+    /// `strongest_paths[i][j]` is the strength of the strongest path from
+    /// option `i` to option `j`.
+    pub strongest_paths: Vec<Vec<i64>>,
+    /// Options with their rank, starting at 1. Options that beat each other
+    /// in neither direction share a rank.
+    pub ranking: Vec<(String, i64)>,
+    pub winner: Option<String>,
+}
+
+/// `options` are the option ids in poll order.
+pub fn tally(options: &[String], ballots: &[Ballot]) -> SchulzeResult {
+    let pairwise_preferences = get_pairwise_preferences(options, ballots);
+    let strongest_paths = get_strongest_paths(&pairwise_preferences);
+
+    let mut ranking = options
+        .iter()
+        .enumerate()
+        .map(|(i, option)| {
+            let beaten_by = (0..options.len())
+                .filter(|&j| strongest_paths[j][i] > strongest_paths[i][j])
+                .count();
+            (option.to_string(), beaten_by as i64 + 1)
+        })
+        .collect::<Vec<(String, i64)>>();
+    ranking.sort_by_key(|(_, rank)| *rank);
+
+    let winners = ranking
+        .iter()
+        .filter(|(_, rank)| *rank == 1)
+        .collect::<Vec<&(String, i64)>>();
+    let winner = match winners.as_slice() {
+        [(winner, _)] => Some(winner.to_string()),
+        _ => None,
+    };
+
+    SchulzeResult {
+        options: options.to_vec(),
+        pairwise_preferences,
+        strongest_paths,
+        ranking,
+        winner,
+    }
+}
+
+fn get_pairwise_preferences(options: &[String], ballots: &[Ballot]) -> Vec<Vec<i64>> {
+    let mut preferences = vec![vec![0; options.len()]; options.len()];
+    for ballot in ballots {
+        let ranks = options
+            .iter()
+            .map(|option| {
+                ballot
+                    .choices
+                    .iter()
+                    .position(|choice| choice == option)
+                    .unwrap_or(options.len())
+            })
+            .collect::<Vec<usize>>();
+        for i in 0..options.len() {
+            for j in 0..options.len() {
+                if ranks[i] < ranks[j] {
+                    preferences[i][j] += ballot.weight;
+                }
+            }
+        }
+    }
+    preferences
+}
+
+fn get_strongest_paths(preferences: &[Vec<i64>]) -> Vec<Vec<i64>> {
+    let count = preferences.len();
+    let mut paths = vec![vec![0; count]; count];
+    for i in 0..count {
+        for j in 0..count {
+            if i != j && preferences[i][j] > preferences[j][i] {
+                paths[i][j] = preferences[i][j];
+            }
+        }
+    }
+    for i in 0..count {
+        for j in 0..count {
+            if i == j {
+                continue;
+            }
+            for k in 0..count {
+                if i != k && j != k {
+                    paths[j][k] = paths[j][k].max(paths[j][i].min(paths[i][k]));
+                }
+            }
+        }
+    }
+    paths
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tally::test_helpers::{ballot, options};
+
+    #[test]
+    fn ranks_the_wikipedia_example() {
+        let ballots = vec![
+            ballot("ACBED", 5),
+            ballot("ADECB", 5),
+            ballot("BEDAC", 8),
+            ballot("CABED", 3),
+            ballot("CAEBD", 7),
+            ballot("CBADE", 2),
+            ballot("DCEBA", 7),
+            ballot("EBADC", 8),
+        ];
+
+        let result = tally(&options("ABCDE"), &ballots);
+
+        assert_eq!(
+            result.pairwise_preferences,
+            vec![
+                vec![0, 20, 26, 30, 22],
+                vec![25, 0, 16, 33, 18],
+                vec![19, 29, 0, 17, 24],
+                vec![15, 12, 28, 0, 14],
+                vec![23, 27, 21, 31, 0],
+            ]
+        );
+        assert_eq!(
+            result.strongest_paths,
+            vec![
+                vec![0, 28, 28, 30, 24],
+                vec![25, 0, 28, 33, 24],
+                vec![25, 29, 0, 29, 24],
+                vec![25, 28, 28, 0, 24],
+                vec![25, 28, 28, 31, 0],
+            ]
+        );
+        assert_eq!(
+            result.ranking,
+            vec![
+                ("E".to_string(), 1),
+                ("A".to_string(), 2),
+                ("C".to_string(), 3),
+                ("B".to_string(), 4),
+                ("D".to_string(), 5),
+            ]
+        );
+        assert_eq!(result.winner, Some("E".to_string()));
+    }
+
+    #[test]
+    fn shares_the_rank_of_tied_options_without_a_winner() {
+        let ballots = vec![ballot("AB", 2), ballot("BA", 2), ballot("C", 1)];
+
+        let result = tally(&options("ABC"), &ballots);
+
+        assert_eq!(
+            result.ranking,
+            vec![
+                ("A".to_string(), 1),
+                ("B".to_string(), 1),
+                ("C".to_string(), 3),
+            ]
+        );
+        assert_eq!(result.winner, None);
+    }
+}
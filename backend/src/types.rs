@@ -0,0 +1,211 @@
+use diesel::deserialize::{self, FromSql};
+use diesel::pg::Pg;
+use diesel::serialize::{self, IsNull, Output, ToSql};
+use diesel::sql_types::Varchar;
+use std::fmt;
+use std::io::Write;
+
+macro_rules! varchar_enum {
+    ($(#[$meta:meta])* $name:ident { $($variant:ident => $value:literal),+ $(,)? }) => {
+        $(#[$meta])*
+        #[derive(AsExpression, FromSqlRow, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+        #[sql_type = "Varchar"]
+        pub enum $name {
+            $(
+                #[serde(rename = $value)]
+                $variant,
+            )+
+        }
+
+        impl $name {
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    $($name::$variant => $value,)+
+                }
+            }
+        }
+
+        impl ToSql<Varchar, Pg> for $name {
+            fn to_sql<W: Write>(&self, out: &mut Output<W, Pg>) -> serialize::Result {
+                out.write_all(self.as_str().as_bytes())?;
+                Ok(IsNull::No)
+            }
+        }
+
+        impl FromSql<Varchar, Pg> for $name {
+            fn from_sql(bytes: Option<&[u8]>) -> deserialize::Result<Self> {
+                match not_none!(bytes) {
+                    $(value if value == $value.as_bytes() => Ok($name::$variant),)+
+                    value => Err(format!(
+                        "Unrecognized {} value: {}",
+                        stringify!($name),
+                        String::from_utf8_lossy(value)
+                    )
+                    .into()),
+                }
+            }
+        }
+    };
+}
+
+varchar_enum!(PollType {
+    YesNo => "YES_NO",
+    SingleChoice => "SINGLE_CHOICE",
+    InstantRunoff => "INSTANT_RUNOFF",
+    Schulze => "SCHULZE",
+    Approval => "APPROVAL",
+    Score => "SCORE",
+});
+
+varchar_enum!(PresetAnswer {
+    Accept => "ACCEPT",
+    Decline => "DECLINE",
+    Abstain => "ABSTAIN",
+});
+
+varchar_enum!(
+    /// The majority a poll needs to be decided.
+    ///
+    /// - `SIMPLE`: more accept than decline votes on `YES_NO` polls. Every
+    ///   other poll type is decided by its own method without further
+    ///   requirements.
+    /// - `ABSOLUTE`: the winning option needs the votes of more than half of
+    ///   all voters of the voting.
+    /// - `TWO_THIRDS`: the winning option needs at least two thirds of the
+    ///   votes cast. Abstentions are not counted as cast.
+    ///
+    /// `ABSOLUTE` and `TWO_THIRDS` are only available on polls where every
+    /// vote goes to the options directly, see
+    /// [`PollType::supports_majority_rules`].
+    MajorityRule {
+        Simple => "SIMPLE",
+        Absolute => "ABSOLUTE",
+        TwoThirds => "TWO_THIRDS",
+    }
+);
+
+varchar_enum!(
+    /// How a poll is decided when options are tied for the lead.
+    ///
+    /// - `DRAW`: the tie stays unresolved.
+    /// - `REJECT`: the poll is rejected, `YES_NO` polls count as declined.
+    /// - `CHAIR_DECIDES`: the admin records which of the tied options wins.
+    /// - `CASTING_VOTER`: the ballot of the voting's casting voter decides
+    ///   between the tied options.
+    TiePolicy {
+        Draw => "DRAW",
+        Reject => "REJECT",
+        ChairDecides => "CHAIR_DECIDES",
+        CastingVoter => "CASTING_VOTER",
+    }
+);
+
+varchar_enum!(
+    /// Whether a poll counts every voter once (`HEADS`) or by the weight of
+    /// their vote (`WEIGHTS`). Decides which counts the result and the
+    /// majority and quorum checks use.
+    VoteCounting {
+        Heads => "HEADS",
+        Weights => "WEIGHTS",
+    }
+);
+
+varchar_enum!(
+    /// The lifecycle of a poll. A `DRAFT` poll is opened on its own, at its
+    /// `opens_at` or when the agenda reaches it, and several polls can be open
+    /// at once. An `OPEN` poll is closed on its own, at its `closes_at` or
+    /// when the agenda moves on from it. A `CLOSED` poll only accepts votes
+    /// again after it is reopened.
+    PollState {
+        Draft => "DRAFT",
+        Open => "OPEN",
+        Closed => "CLOSED",
+    }
+);
+
+varchar_enum!(
+    /// What an admin key of a voting may do. Every admin key can read the
+    /// voting and its results.
+    ///
+    /// - `FULL`: everything, including changing the polls and the admin keys.
+    /// - `VOTERS`: manage the voters, their proxies and their attendance.
+    /// - `AGENDA`: open and close polls and move through the agenda.
+    /// - `RESULTS`: only read the voting and its results.
+    AdminScope {
+        Full => "FULL",
+        Voters => "VOTERS",
+        Agenda => "AGENDA",
+        Results => "RESULTS",
+    }
+);
+
+impl PollType {
+    /// Whether voters rank several options instead of choosing one.
+    pub fn is_ranked(&self) -> bool {
+        match self {
+            PollType::InstantRunoff | PollType::Schulze => true,
+            PollType::YesNo | PollType::SingleChoice | PollType::Approval | PollType::Score => {
+                false
+            }
+        }
+    }
+
+    pub fn supports_majority_rules(&self) -> bool {
+        match self {
+            PollType::YesNo | PollType::SingleChoice | PollType::Approval => true,
+            PollType::InstantRunoff | PollType::Schulze | PollType::Score => false,
+        }
+    }
+}
+
+impl PresetAnswer {
+    pub fn from_answer(answer: &Option<bool>) -> PresetAnswer {
+        match answer {
+            None => PresetAnswer::Abstain,
+            Some(true) => PresetAnswer::Accept,
+            Some(false) => PresetAnswer::Decline,
+        }
+    }
+
+    pub fn option_name(&self) -> &'static str {
+        match self {
+            PresetAnswer::Accept => "Accept",
+            PresetAnswer::Decline => "Decline",
+            PresetAnswer::Abstain => "Abstain",
+        }
+    }
+}
+
+impl AdminScope {
+    /// Whether a key with this scope can do what needs the given scope.
+    pub fn allows(&self, scope: AdminScope) -> bool {
+        match (self, scope) {
+            (AdminScope::Full, _) | (_, AdminScope::Results) => true,
+            (own_scope, scope) => *own_scope == scope,
+        }
+    }
+}
+
+/// The options every `YES_NO` poll is created with, in ballot order.
+pub const YES_NO_PRESET: [PresetAnswer; 3] = [
+    PresetAnswer::Accept,
+    PresetAnswer::Decline,
+    PresetAnswer::Abstain,
+];
+
+/// A poll addressed by its id or, for compatibility, by its position in the
+/// agenda. The position changes when polls are reordered or deleted.
+#[derive(Clone, Debug)]
+pub enum PollKey {
+    Index(i32),
+    Id(String),
+}
+
+impl fmt::Display for PollKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PollKey::Index(index) => write!(f, "index: {}", index),
+            PollKey::Id(id) => write!(f, "id: {}", id),
+        }
+    }
+}
@@ -44,6 +44,68 @@ impl<'r> Responder<'r> for ErrorResponse {
     }
 }
 
+/// An `ErrorResponse` of a bulk request that lists every invalid row.
+#[derive(Debug)]
+pub struct RowErrorsResponse {
+    pub reason: String,
+    pub status: Status,
+    pub row_errors: Vec<RowError>,
+}
+
+/// Rows are counted from 1, without a header row.
+#[derive(Serialize, Debug)]
+pub struct RowError {
+    pub row: usize,
+    pub reason: String,
+}
+
+impl From<ErrorResponse> for RowErrorsResponse {
+    fn from(error: ErrorResponse) -> RowErrorsResponse {
+        RowErrorsResponse {
+            reason: error.reason,
+            status: error.status,
+            row_errors: Vec::new(),
+        }
+    }
+}
+
+impl<'r> Responder<'r> for RowErrorsResponse {
+    fn respond_to(self, _: &Request) -> response::Result<'r> {
+        Response::build()
+            .sized_body(Cursor::new(
+                json!({
+                    "reason": self.reason,
+                    "status": self.status.code,
+                    "rowErrors": self.row_errors
+                })
+                .to_string(),
+            ))
+            .status(self.status)
+            .header(ContentType::JSON)
+            .ok()
+    }
+}
+
+/// A CSV file the browser downloads instead of showing it.
+#[derive(Debug)]
+pub struct CsvAttachment {
+    pub filename: String,
+    pub body: String,
+}
+
+impl<'r> Responder<'r> for CsvAttachment {
+    fn respond_to(self, _: &Request) -> response::Result<'r> {
+        Response::build()
+            .sized_body(Cursor::new(self.body))
+            .header(ContentType::CSV)
+            .raw_header(
+                "Content-Disposition",
+                format!("attachment; filename=\"{}\"", self.filename),
+            )
+            .ok()
+    }
+}
+
 #[derive(Debug)]
 pub struct AuthenticatedUser {
     pub key_hash: String,
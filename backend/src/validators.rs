@@ -1,11 +1,13 @@
 use crate::dtos::{create_voter_dto, create_voting_dto};
+use crate::types::{MajorityRule, PollType, TiePolicy, VoteCounting};
 use crate::utils::ErrorResponse;
 
+use chrono::NaiveDateTime;
 use rocket::http::Status;
 use rocket_contrib::json::Json;
 
 pub fn validate_create_voting_request(
-    input: &Json<create_voting_dto::CreateVotingRequest>,
+    input: &create_voting_dto::CreateVotingRequest,
 ) -> Result<(), ErrorResponse> {
     match input.name.len() {
         5..=60 => Ok(()),
@@ -21,13 +23,102 @@ pub fn validate_create_voting_request(
             status: Status::BadRequest,
         }),
     }?;
+    match input.max_proxies_per_voter {
+        None | Some(0..=100) => Ok(()),
+        _ => Err(ErrorResponse {
+            reason: "Voting maxProxiesPerVoter must be between 0 and 100".to_string(),
+            status: Status::BadRequest,
+        }),
+    }?;
+    match (input.secret, input.tie_policy) {
+        (Some(true), Some(TiePolicy::CastingVoter)) => Err(ErrorResponse {
+            reason: "Tie policy CASTING_VOTER is not possible in a secret voting".to_string(),
+            status: Status::BadRequest,
+        }),
+        _ => Ok(()),
+    }?;
+    match (input.allow_vote_change, input.max_vote_changes) {
+        (_, None) | (Some(true), Some(1..=100)) => Ok(()),
+        (Some(true), _) => Err(ErrorResponse {
+            reason: "Voting maxVoteChanges must be between 1 and 100".to_string(),
+            status: Status::BadRequest,
+        }),
+        _ => Err(ErrorResponse {
+            reason: "Voting maxVoteChanges needs allowVoteChange".to_string(),
+            status: Status::BadRequest,
+        }),
+    }?;
+    match (input.secret, input.allow_vote_change) {
+        (Some(true), Some(true)) => Err(ErrorResponse {
+            reason: "Votes can not be changed in a secret voting".to_string(),
+            status: Status::BadRequest,
+        }),
+        _ => Ok(()),
+    }?;
+    for poll in &input.polls {
+        validate_poll_vote_counting(input.secret.unwrap_or(false), poll)?;
+    }
     validate_create_voting_polls_request(&input.polls)
 }
 
+/// Secret ballots are stored without the weight of the voter, so their polls
+/// can only count heads.
+pub fn validate_poll_vote_counting(
+    secret: bool,
+    poll: &create_voting_dto::CreateVotingPollRequest,
+) -> Result<(), ErrorResponse> {
+    match (secret, poll.vote_counting) {
+        (true, Some(VoteCounting::Weights)) => Err(ErrorResponse {
+            reason: "Vote counting WEIGHTS is not possible in a secret voting".to_string(),
+            status: Status::BadRequest,
+        }),
+        _ => Ok(()),
+    }
+}
+
+pub fn validate_template_voting_request(
+    input: &create_voting_dto::CreateVotingRequest,
+) -> Result<(), ErrorResponse> {
+    match input.template {
+        None => Ok(()),
+        Some(_) => Err(ErrorResponse {
+            reason: "A template can not start from another template".to_string(),
+            status: Status::BadRequest,
+        }),
+    }?;
+    validate_create_voting_request(input)
+}
+
+pub fn validate_template_name(name: &str) -> Result<(), ErrorResponse> {
+    match name.len() {
+        1..=60 => Ok(()),
+        _ => Err(ErrorResponse {
+            reason: "Template name length must be between 1 and 60 characters".to_string(),
+            status: Status::BadRequest,
+        }),
+    }
+}
+
+pub fn validate_admin_key_label(label: &str) -> Result<(), ErrorResponse> {
+    match label.len() {
+        1..=60 => Ok(()),
+        _ => Err(ErrorResponse {
+            reason: "Admin key label length must be between 1 and 60 characters".to_string(),
+            status: Status::BadRequest,
+        }),
+    }
+}
+
 pub fn validate_create_voter_request(
     input: &Json<create_voter_dto::CreateVoterRequest>,
 ) -> Result<(), ErrorResponse> {
-    match input.username.len() {
+    validate_voter_username(&input.username)?;
+    validate_voter_weight(input.weight)?;
+    validate_voter_email(&input.email)
+}
+
+pub fn validate_voter_username(username: &str) -> Result<(), ErrorResponse> {
+    match username.len() {
         5..=60 => Ok(()),
         _ => Err(ErrorResponse {
             reason: "Voter username length must be between 5 and 60 characters".to_string(),
@@ -36,6 +127,26 @@ pub fn validate_create_voter_request(
     }
 }
 
+pub fn validate_voter_weight(weight: Option<i32>) -> Result<(), ErrorResponse> {
+    match weight {
+        None | Some(1..=1_000_000) => Ok(()),
+        _ => Err(ErrorResponse {
+            reason: "Voter weight must be between 1 and 1000000".to_string(),
+            status: Status::BadRequest,
+        }),
+    }
+}
+
+pub fn validate_voter_email(email: &Option<String>) -> Result<(), ErrorResponse> {
+    match email {
+        Some(email) if email.len() > 254 || !email.contains('@') => Err(ErrorResponse {
+            reason: format!("Voter email: {} is not a valid email address", email),
+            status: Status::BadRequest,
+        }),
+        _ => Ok(()),
+    }
+}
+
 pub fn validate_voting_id(voting_id: &String) -> Result<(), ErrorResponse> {
     let len = voting_id.len();
     match len {
@@ -47,24 +158,207 @@ pub fn validate_voting_id(voting_id: &String) -> Result<(), ErrorResponse> {
     }
 }
 
+pub fn validate_voter_id(voter_id: &str) -> Result<(), ErrorResponse> {
+    let len = voter_id.len();
+    match len {
+        36 => Ok(()),
+        _ => Err(ErrorResponse {
+            reason: format!("Voter id must be of fixed lenght of: 36 was: {}", len),
+            status: Status::BadRequest,
+        }),
+    }
+}
+
+pub fn validate_poll_id(poll_id: &str) -> Result<(), ErrorResponse> {
+    let len = poll_id.len();
+    match len {
+        36 => Ok(()),
+        _ => Err(ErrorResponse {
+            reason: format!("Poll id must be of fixed lenght of: 36 was: {}", len),
+            status: Status::BadRequest,
+        }),
+    }
+}
+
+pub fn validate_admin_key_id(admin_key_id: &str) -> Result<(), ErrorResponse> {
+    let len = admin_key_id.len();
+    match len {
+        36 => Ok(()),
+        _ => Err(ErrorResponse {
+            reason: format!("Admin key id must be of fixed lenght of: 36 was: {}", len),
+            status: Status::BadRequest,
+        }),
+    }
+}
+
 fn validate_create_voting_polls_request(
     polls: &Vec<create_voting_dto::CreateVotingPollRequest>,
 ) -> Result<(), ErrorResponse> {
     for poll in polls {
-        match poll.name.len() {
-            5..=60 => Ok(()),
-            _ => Err(ErrorResponse {
-                reason: "Poll Name length must be between 5 and 60 characters".to_string(),
+        validate_create_poll_request(poll)?;
+    }
+
+    Ok(())
+}
+
+pub fn validate_create_poll_request(
+    poll: &create_voting_dto::CreateVotingPollRequest,
+) -> Result<(), ErrorResponse> {
+    validate_poll_name(&poll.name)?;
+    validate_poll_description(&poll.description)?;
+    match (poll.get_poll_type(), &poll.options) {
+        (PollType::YesNo, None) => Ok(()),
+        (PollType::YesNo, Some(_)) => Err(ErrorResponse {
+            reason: "Poll of type YES_NO can not have options".to_string(),
+            status: Status::BadRequest,
+        }),
+        (_, Some(options)) => validate_create_voting_poll_options_request(options),
+        (poll_type, None) => Err(ErrorResponse {
+            reason: format!("Poll of type {} must have options", poll_type.as_str()),
+            status: Status::BadRequest,
+        }),
+    }?;
+    validate_create_voting_poll_settings_request(poll)?;
+    if let Some(topic) = &poll.topic {
+        validate_topic(topic)?;
+    }
+    validate_poll_schedule(&poll.opens_at, &poll.closes_at)?;
+    match poll.time_limit_seconds {
+        None | Some(10..=86_400) => Ok(()),
+        _ => Err(ErrorResponse {
+            reason: "Poll timeLimitSeconds must be between 10 and 86400".to_string(),
+            status: Status::BadRequest,
+        }),
+    }
+}
+
+pub fn validate_poll_name(name: &str) -> Result<(), ErrorResponse> {
+    match name.len() {
+        5..=60 => Ok(()),
+        _ => Err(ErrorResponse {
+            reason: "Poll Name length must be between 5 and 60 characters".to_string(),
+            status: Status::BadRequest,
+        }),
+    }
+}
+
+pub fn validate_poll_description(description: &str) -> Result<(), ErrorResponse> {
+    match description.len() {
+        5..=60 => Ok(()),
+        _ => Err(ErrorResponse {
+            reason: "Poll Description length must be between 5 and 60 characters".to_string(),
+            status: Status::BadRequest,
+        }),
+    }
+}
+
+pub fn validate_poll_schedule(
+    opens_at: &Option<NaiveDateTime>,
+    closes_at: &Option<NaiveDateTime>,
+) -> Result<(), ErrorResponse> {
+    match (opens_at, closes_at) {
+        (Some(opens_at), Some(closes_at)) if opens_at >= closes_at => Err(ErrorResponse {
+            reason: "Poll closesAt must be after opensAt".to_string(),
+            status: Status::BadRequest,
+        }),
+        _ => Ok(()),
+    }
+}
+
+pub fn validate_topic(topic: &str) -> Result<(), ErrorResponse> {
+    match topic.len() {
+        1..=60 => Ok(()),
+        _ => Err(ErrorResponse {
+            reason: "Topic length must be between 1 and 60 characters".to_string(),
+            status: Status::BadRequest,
+        }),
+    }
+}
+
+fn validate_create_voting_poll_settings_request(
+    poll: &create_voting_dto::CreateVotingPollRequest,
+) -> Result<(), ErrorResponse> {
+    let poll_type = poll.get_poll_type();
+    if poll_type != PollType::Approval
+        && (poll.min_approvals.is_some() || poll.max_approvals.is_some())
+    {
+        return Err(ErrorResponse {
+            reason: "Only polls of type APPROVAL can have minApprovals or maxApprovals".to_string(),
+            status: Status::BadRequest,
+        });
+    }
+    if poll_type != PollType::Score && poll.max_score.is_some() {
+        return Err(ErrorResponse {
+            reason: "Only polls of type SCORE can have a maxScore".to_string(),
+            status: Status::BadRequest,
+        });
+    }
+    if let Some((min_approvals, max_approvals)) = poll.get_approval_bounds() {
+        let options_count = poll.options.as_ref().map_or(0, |options| options.len()) as i32;
+        if min_approvals < 0
+            || min_approvals > max_approvals
+            || max_approvals < 1
+            || max_approvals > options_count
+        {
+            return Err(ErrorResponse {
+                reason: format!(
+                    "Poll approvals must be between 0 and {} and minApprovals can not be larger than maxApprovals",
+                    options_count
+                ),
                 status: Status::BadRequest,
-            }),
-        }?;
-        match poll.description.len() {
-            5..=60 => Ok(()),
+            });
+        }
+    }
+    match poll.get_max_score() {
+        None | Some(1..=100) => Ok(()),
+        _ => Err(ErrorResponse {
+            reason: "Poll maxScore must be between 1 and 100".to_string(),
+            status: Status::BadRequest,
+        }),
+    }?;
+    match poll.majority_rule {
+        None | Some(MajorityRule::Simple) => Ok(()),
+        Some(_) if poll_type.supports_majority_rules() => Ok(()),
+        Some(majority_rule) => Err(ErrorResponse {
+            reason: format!(
+                "Poll of type {} can not have the majorityRule {}",
+                poll_type.as_str(),
+                majority_rule.as_str()
+            ),
+            status: Status::BadRequest,
+        }),
+    }?;
+    match poll.quorum_percent {
+        None | Some(1..=100) => Ok(()),
+        _ => Err(ErrorResponse {
+            reason: "Poll quorumPercent must be between 1 and 100".to_string(),
+            status: Status::BadRequest,
+        }),
+    }
+}
+
+fn validate_create_voting_poll_options_request(options: &[String]) -> Result<(), ErrorResponse> {
+    match options.len() {
+        2..=20 => Ok(()),
+        _ => Err(ErrorResponse {
+            reason: "Poll must have between 2 and 20 options".to_string(),
+            status: Status::BadRequest,
+        }),
+    }?;
+    for (i, option) in options.iter().enumerate() {
+        match option.len() {
+            1..=60 => Ok(()),
             _ => Err(ErrorResponse {
-                reason: "Poll Description length must be between 5 and 60 characters".to_string(),
+                reason: "Poll Option length must be between 1 and 60 characters".to_string(),
                 status: Status::BadRequest,
             }),
         }?;
+        if options[..i].contains(option) {
+            return Err(ErrorResponse {
+                reason: format!("Poll Option: {} is not unique", option),
+                status: Status::BadRequest,
+            });
+        }
     }
 
     Ok(())